@@ -1,5 +1,6 @@
 use super::Day;
 use std::fs;
+use crate::util::vec2d::Turn;
 
 pub struct Day1;
 
@@ -31,25 +32,9 @@ impl Day<Vec<Rotation>> for Day1 {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Copy)]
-pub enum Direction {
-    Right,
-    Left,
-}
-
-impl Direction {
-    fn from_str(letter: &str) -> Self {
-        match letter {
-            "R" => Direction::Right,
-            "L" => Direction::Left,
-            _ => panic!("Invalid direction {letter}")
-        }
-    }
-}
-
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Rotation {
-    direction: Direction,
+    turn: Turn,
     amount: i32,
 }
 
@@ -64,9 +49,9 @@ impl Dial {
     }
 
     fn turn(&mut self, rotation: &Rotation) {
-        match rotation.direction {
-            Direction::Left => self.raw_position -= rotation.amount,
-            Direction::Right => self.raw_position += rotation.amount
+        match rotation.turn {
+            Turn::Left => self.raw_position -= rotation.amount,
+            Turn::Right => self.raw_position += rotation.amount
         }
     }
 
@@ -91,10 +76,10 @@ impl Dial {
 
 fn parse_input(input: &str) -> Vec<Rotation> {
     input.lines().map( |l| {
-        let direction = Direction::from_str(&l[..1]);
+        let turn = Turn::try_from(l.chars().next().unwrap()).unwrap();
         let amount = l[1..].parse().unwrap();
         Rotation {
-            direction,
+            turn,
             amount
         }
     })
@@ -2,6 +2,7 @@ use fancy_regex::Regex;
 
 use super::Day;
 use std::fs;
+use crate::util::num::Checked;
 
 pub struct Day2;
 
@@ -17,9 +18,9 @@ impl Day<Vec<(i64, i64)>> for Day2 {
             .filter(|product| {
                 let product = product.to_string();
                 let half = product.len() / 2;
-                &product[0..half] == &product[half..]
+                product[0..half] == product[half..]
             })
-            .sum::<i64>()
+            .sum::<Checked<i64>>()
     }
 
     fn part2(input: &Vec<(i64, i64)>) -> impl std::fmt::Display {
@@ -33,7 +34,7 @@ impl Day<Vec<(i64, i64)>> for Day2 {
         input.iter()
             .flat_map(|&(start, end)| start ..= end)
             .filter(|product| re.is_match(&product.to_string()).unwrap())
-            .sum::<i64>()
+            .sum::<Checked<i64>>()
     }
 }
 
@@ -1,17 +1,19 @@
 use super::Day;
 use std::fs;
+use crate::util::num::Checked;
+use crate::util::grid::prelude::*;
 
 pub struct Day3;
 
-impl Day<Vec<Vec<u32>>> for Day3 {
-    fn read_input() -> Vec<Vec<u32>> {
+impl Day<Vec2d<u32>> for Day3 {
+    fn read_input() -> Vec2d<u32> {
         let input = fs::read_to_string("resources/day3.txt").expect("file day3.txt not found");
         parse_input(&input)
     }
 
     // Solved in O(n) using 2 pointers
-    fn part1(input: &Vec<Vec<u32>>) -> impl std::fmt::Display {
-        input.iter().map(|bank|{
+    fn part1(input: &Vec2d<u32>) -> impl std::fmt::Display {
+        input.rows().map(|bank|{
             let mut p1 = 0;
             let mut p2 = 0;
             for i in 0 .. bank.len() {
@@ -31,8 +33,8 @@ impl Day<Vec<Vec<u32>>> for Day3 {
     // welp
     // same approach, but with 12 pointers
     // use an array to track the 12 pointers
-    fn part2(input: &Vec<Vec<u32>>) -> impl std::fmt::Display {
-        input.iter().map(|bank|{
+    fn part2(input: &Vec2d<u32>) -> impl std::fmt::Display {
+        input.rows().map(|bank|{
             // 12 pointers in an array
             let mut p = [0,0,0,0,0,0,0,0,0,0,0,0];
 
@@ -43,8 +45,8 @@ impl Day<Vec<Vec<u32>>> for Day3 {
                     if i <= bank.len() - offset && bank[i] > p[n] {
                         p[n] = bank[i];
                         // zero out the pointers to the right of the one just updated
-                        for n2 in n + 1 .. 12 {
-                            p[n2] = 0;
+                        for pointer in p.iter_mut().skip(n + 1) {
+                            *pointer = 0;
                         }
                         break;
                     } 
@@ -57,18 +59,12 @@ impl Day<Vec<Vec<u32>>> for Day3 {
                 .parse::<i64>().unwrap()
 
         })
-        .sum::<i64>()
+        .sum::<Checked<i64>>()
     }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
-    input.lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap())
-                .collect()
-        })
-        .collect()
+fn parse_input(input: &str) -> Vec2d<u32> {
+    Vec2d::parse(input, |c| c.to_digit(10).unwrap()).unwrap()
 }
 
 #[cfg(test)]
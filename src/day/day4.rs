@@ -1,65 +1,42 @@
 use super::Day;
 use std::fs;
+use crate::util::automaton::Automaton;
+use crate::util::bitgrid::BitGrid;
 use crate::util::grid::prelude::*;
 
 pub struct Day4;
 
-impl Day<Vec2d<bool>> for Day4 {
-    fn read_input() -> Vec2d<bool> {
+impl Day<BitGrid> for Day4 {
+    fn read_input() -> BitGrid {
         let input = fs::read_to_string("resources/day4.txt").expect("file day4.txt not found");
         parse_input(&input)
     }
 
-    fn part1(input: &Vec2d<bool>) -> impl std::fmt::Display {
-        input.grid.iter().enumerate()
-            .filter(|&(_, paper)| *paper)
-            .filter(|(idx, _)| {
-                let point = input.idx_to_point(*idx);
-                let num_adjacent = count_adjacent(input, point);
-                num_adjacent < 4
-            })
-            .count()
+    fn part1(input: &BitGrid) -> impl std::fmt::Display {
+        accessible(input).count_ones()
     }
 
-    fn part2(input: &Vec2d<bool>) -> impl std::fmt::Display {
-        let mut grid = input.clone();
-        let mut total_removed = 0;
-        loop {
-            let to_remove = grid.grid.iter().enumerate()
-                .filter(|&(_, paper)| *paper)
-                .map(|(idx, _)| grid.idx_to_point(idx))
-                .filter(|&point| count_adjacent(&grid, point) < 4)
-                .collect::<Vec<_>>();
-            if to_remove.len() == 0 {
-                break;
-            }
-            total_removed += to_remove.len();
-            to_remove.into_iter().for_each(| point | grid[point] = false);
-        }
-        total_removed
+    fn part2(input: &BitGrid) -> impl std::fmt::Display {
+        // every accessible roll is removed at once, until no more can be removed
+        let mut automaton = Automaton::bits(input.clone(), Connectivity::Eight, |grid, counts| grid & &counts.at_least(4));
+        automaton.run();
+        automaton.history().iter().map(|step| step.changed).sum::<usize>()
     }
 }
 
-fn count_adjacent(grid: &Vec2d<bool>, point: Point) -> usize {
-    const DIRECTIONS: [Directions; 8] = [Directions::Down, Directions::DownLeft, Directions::DownRight, Directions::Left,
-        Directions::Right, Directions::Up, Directions::UpLeft, Directions::UpRight];
-    DIRECTIONS.iter()
-        .filter_map(|&d| grid.next_point(point, d))
-        .filter(|&adjacent_point| grid[adjacent_point])
-        .count()
+/// Rolls of paper with fewer than 4 rolls in the 8 adjacent positions
+fn accessible(grid: &BitGrid) -> BitGrid {
+    grid.and_not(&grid.neighbor_counts(Connectivity::Eight).at_least(4))
 }
 
-fn parse_input(input: &str) -> Vec2d<bool> {
-    let bools = input.lines()
-        .flat_map(|line| line.trim().chars().map(|c| c == '@').collect::<Vec<_>>())
-        .collect();
-    let line_len = input.lines().next().unwrap().len() as i32;
-    Vec2d { grid: bools, line_len }
+fn parse_input(input: &str) -> BitGrid {
+    BitGrid::from(&Vec2d::parse(input, |c| c == '@').unwrap())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::{Duration, Instant};
 
     const TEST: &str = "..@@.@@@@.
 @@@.@.@.@@
@@ -85,4 +62,30 @@ mod tests {
         let result = Day4::part2(&input);
         assert_eq!("43", result.to_string());
     }
+
+    /// Compares part 2 on the real input against the same rule stepped cell by cell on a `Vec2d`
+    #[test]
+    #[ignore = "benchmark, run with `cargo test --release bench_part2 -- --ignored --nocapture`"]
+    fn bench_part2() {
+        const RUNS: u32 = 20;
+        fn time(f: impl Fn() -> usize) -> (usize, Duration) {
+            let now = Instant::now();
+            let result = (0 .. RUNS).map(|_| f()).last().unwrap();
+            (result, now.elapsed() / RUNS)
+        }
+
+        let input = fs::read_to_string("resources/day4.txt").expect("file day4.txt not found");
+        let cells = Vec2d::parse(&input, |c| c == '@').unwrap();
+        let bits = parse_input(&input);
+        let (expected, cell_time) = time(|| {
+            let mut automaton = Automaton::new(cells.clone(), Connectivity::Eight, |&paper, neighbors| {
+                paper && neighbors.iter().filter(|&&&paper| paper).count() >= 4
+            });
+            automaton.run();
+            automaton.history().iter().map(|step| step.changed).sum()
+        });
+        let (result, bit_time) = time(|| Day4::part2(&bits).to_string().parse().unwrap());
+        assert_eq!(expected, result);
+        println!("Vec2d: {cell_time:?}, BitGrid: {bit_time:?}, {:.1}x faster", cell_time.as_secs_f64() / bit_time.as_secs_f64());
+    }
 }
@@ -1,5 +1,6 @@
 use super::Day;
 use std::fs;
+use crate::util::num::Checked;
 
 pub struct Day5;
 
@@ -51,7 +52,7 @@ impl Day<Inventory> for Day5 {
         }
         final_ranges.iter()
             .map(|(start, end)| end - start + 1) //+ 1 because of the inclusive range
-            .sum::<i64>()
+            .sum::<Checked<i64>>()
     }
 }
 
@@ -1,5 +1,7 @@
 use super::Day;
-use std::fs;
+use std::{fs, iter};
+use crate::util::num::Checked;
+use crate::util::grid::prelude::*;
 
 pub struct Day6;
 
@@ -9,12 +11,13 @@ impl Day<String> for Day6 {
     }
 
     fn part1(input: &String) -> impl std::fmt::Display {
-        // flip the 2d array so we can easily compute problems top down
-        let problems = as_top_down(input.as_str());
-        problems.iter().map(|row| {
-            let operation = row.last().unwrap().as_str();
-            let result = &row[0 .. row.len() - 1].iter()
-                .map(|num| num.parse::<i64>().unwrap())
+        // read the grid of numbers by column so we can easily compute problems top down
+        let problems = Vec2d::from_rows(input.lines().map(|line| line.split_whitespace())).unwrap();
+        problems.cols().map(|column| {
+            let column = column.collect::<Vec<_>>();
+            let operation = *column[column.len() - 1];
+            let result = &column[0 .. column.len() - 1].iter()
+                .map(|num| Checked(num.parse::<i64>().unwrap()))
                 .reduce(|a, b| match operation {
                     "+" => a + b,
                     "*" => a * b,
@@ -22,32 +25,29 @@ impl Day<String> for Day6 {
                 }).unwrap();
             *result
         })
-        .sum::<i64>()
+        .sum::<Checked<i64>>()
     }
 
     fn part2(input: &String) -> impl std::fmt::Display {
         let lines = input.lines().collect::<Vec<_>>();
         // the last line is the list of operators (+ or *)
-        let operations = lines.last().unwrap().trim().split_whitespace().rev().collect::<Vec<_>>();
+        let operations = lines.last().unwrap().split_whitespace().rev().collect::<Vec<_>>();
         let length = lines.iter().map(|line| line.len()).max().unwrap();
 
-        // turn the remaining lines into char arrays for indexing
-        let lines = &lines[0..lines.len() -1].iter()
-            .map(|l| l.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
+        // pad the remaining lines into a grid, then rotate it so that reading
+        // right to left, top to bottom becomes reading each row in order
+        let digits = Vec2d::from_rows(lines[0..lines.len() -1].iter()
+            .map(|l| l.chars().chain(iter::repeat(' ')).take(length)))
+            .unwrap()
+            .rotate_ccw();
         let mut all_problems = vec![];
         let mut current_problemset = vec![];
-        // loop right to left, top to bottom
-        for i in (0 .. length).rev() {
-            let mut problem_line = vec![];
-            for line in lines{
-                let c = line[i];
-                if !c.is_whitespace(){
-                    problem_line.push(c);
-                }
-            }
+        for row in digits.rows() {
+            let problem_line = row.iter()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>();
             if !problem_line.is_empty() {
-                let num = problem_line.iter().collect::<String>().parse::<i64>().unwrap();
+                let num = problem_line.parse::<i64>().unwrap();
                 current_problemset.push(num);
             } else {
                 // a line of all whitespace separates this set of numbers/opertor from the next set
@@ -58,32 +58,16 @@ impl Day<String> for Day6 {
         all_problems.push(current_problemset);
 
         // recombine the problem set with the associated operator
-        all_problems.into_iter().zip(operations.into_iter())
+        all_problems.into_iter().zip(operations)
             .map(|(problemset, operator)| {
-                problemset.into_iter().reduce(|a, b| match operator {
+                problemset.into_iter().map(Checked).reduce(|a, b| match operator {
                     "+" => a + b,
                     "*" => a * b,
                     _ => panic!("Invalid operation")
                 }).unwrap()
             })
-            .sum::<i64>()
-    }
-}
-
-fn as_top_down(input: &str) -> Vec<Vec<String>> {
-    let lines = input.lines()
-        .map(|line| line.trim().split_whitespace().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let mut result = vec![];
-    for i in 0 .. lines[0].len() {
-        let mut result_line = vec![];
-        for line in lines.iter() {
-            result_line.push(line[i].to_string());
-        }
-        result.push(result_line);
+            .sum::<Checked<i64>>()
     }
-    result
 }
 
 #[cfg(test)]
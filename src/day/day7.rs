@@ -1,5 +1,5 @@
 use super::Day;
-use std::{collections::{HashMap, HashSet}, fs};
+use std::{collections::HashSet, fs};
 use crate::util::grid::prelude::*;
 
 pub struct Day7;
@@ -35,18 +35,18 @@ impl Day<Vec2d<char>> for Day7 {
 
     fn part2(input: &Vec2d<char>) -> impl std::fmt::Display {
         let start = input.find(&'S').unwrap();
-        let mut tachyon_timeline = HashMap::new();
-        tachyon_timeline.insert(start, 1);
-        let grid_height = input.grid.len() as i32 / input.line_len;
-        for _ in 0 .. grid_height - 1{
-            let mut new_timeline = HashMap::new();
-            for (tachyon, count) in tachyon_timeline.into_iter() {
+        // number of timelines that have a tachyon at each point
+        let mut tachyon_timeline = SparseGrid::new(0_i64);
+        tachyon_timeline.set(start, 1);
+        for _ in 0 .. input.height() - 1 {
+            let mut new_timeline = SparseGrid::new(0);
+            for (tachyon, &count) in tachyon_timeline.iter() {
                 let next = input.next_unbounded(tachyon, Directions::Down);
-                if input[next] == '^' {
-                    *new_timeline.entry(Point::new(next.x - 1, next.y)).or_insert(0) += count;
-                    *new_timeline.entry(Point::new(next.x + 1, next.y)).or_insert(0) += count;
+                if input.get(next) == Some(&'^') {
+                    *new_timeline.entry(Point::new(next.x - 1, next.y)) += count;
+                    *new_timeline.entry(Point::new(next.x + 1, next.y)) += count;
                 } else {
-                    *new_timeline.entry(next).or_insert(0) += count;
+                    *new_timeline.entry(next) += count;
                 }
             }
             tachyon_timeline = new_timeline;
@@ -56,11 +56,8 @@ impl Day<Vec2d<char>> for Day7 {
 }
 
 fn parse_input(input: &str) -> Vec2d<char> {
-    let chars = input.lines()
-        .flat_map(|line| line.trim().chars().collect::<Vec<_>>())
-        .collect();
-    let line_len = input.lines().next().unwrap().len() as i32;
-    Vec2d { grid: chars, line_len }
+    // a beam that is split off the side of the manifold keeps going through empty space
+    Vec2d::parse(input, |c| c).unwrap().with_edges(EdgePolicy::Default('.'))
 }
 
 
@@ -1,41 +1,27 @@
 use super::Day;
 use std::{collections::{HashMap, HashSet}, fs};
+use crate::util::num::Checked;
+use crate::util::point3d::Point3d64;
 
 pub struct Day8;
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
-pub struct Point3d {
-    pub x: i64,
-    pub y: i64,
-    pub z: i64,
-}
-
-impl Point3d {
-    fn euclid_distance(&self, other: &Point3d) -> i64 {
-        let square = (self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2);
-        // technically this is squared euclid, but it should be fine for this problem
-        // this issue is the f64 is not Ord, so can't be naturally sorted. Stupid floating points.
-        square
-    }
-}
-
-impl Day<Vec<Point3d>> for Day8 {
-    fn read_input() -> Vec<Point3d> {
+impl Day<Vec<Point3d64>> for Day8 {
+    fn read_input() -> Vec<Point3d64> {
         let input = fs::read_to_string("resources/day8.txt").expect("file day8.txt not found");
         parse_input(&input)
     }
 
-    fn part1(input: &Vec<Point3d>) -> impl std::fmt::Display {
-        let mut distances: HashMap<(Point3d, Point3d), i64> = HashMap::new();
+    fn part1(input: &Vec<Point3d64>) -> impl std::fmt::Display {
+        let mut distances: HashMap<(Point3d64, Point3d64), i64> = HashMap::new();
         for i in 0 .. input.len() - 1 {
             for j in i + 1 .. input.len() {
-                distances.insert((input[i], input[j]), input[i].euclid_distance(&input[j]));
+                distances.insert((input[i], input[j]), input[i].squared_euclid_distance(&input[j]));
             }
         }
         let mut pairs= distances.keys().collect::<Vec<_>>();
         pairs.sort_by_key(|&pair| distances[pair]);
 
-        let mut circuits: Vec<HashSet<Point3d>> = vec![];
+        let mut circuits: Vec<HashSet<Point3d64>> = vec![];
         for pair in &pairs[0..1000] {
             add_circuit_connection(pair, &mut circuits);
         }
@@ -48,17 +34,17 @@ impl Day<Vec<Point3d>> for Day8 {
             .product::<usize>()
     }
 
-    fn part2(input: &Vec<Point3d>) -> impl std::fmt::Display {
-        let mut distances: HashMap<(Point3d, Point3d), i64> = HashMap::new();
+    fn part2(input: &Vec<Point3d64>) -> impl std::fmt::Display {
+        let mut distances: HashMap<(Point3d64, Point3d64), i64> = HashMap::new();
         for i in 0 .. input.len() - 1 {
             for j in i + 1 .. input.len() {
-                distances.insert((input[i], input[j]), input[i].euclid_distance(&input[j]));
+                distances.insert((input[i], input[j]), input[i].squared_euclid_distance(&input[j]));
             }
         }
         let mut pairs= distances.keys().collect::<Vec<_>>();
         pairs.sort_by_key(|&pair| distances[pair]);
 
-        let mut circuits: Vec<HashSet<Point3d>> = vec![];
+        let mut circuits: Vec<HashSet<Point3d64>> = vec![];
         let mut i = 0;
         loop  {
             add_circuit_connection(pairs[i], &mut circuits);
@@ -68,22 +54,22 @@ impl Day<Vec<Point3d>> for Day8 {
             i += 1;
         }
         let (p1, p2) = pairs[i];
-        p1.x * p2.x
+        Checked(p1.x) * p2.x
 
     }
 }
 
-fn add_circuit_connection(pair: &(Point3d, Point3d), circuits: &mut Vec<HashSet<Point3d>>) {
+fn add_circuit_connection(pair: &(Point3d64, Point3d64), circuits: &mut Vec<HashSet<Point3d64>>) {
     let (p1, p2) = pair;
 
     // I wanted to use iter_mut().find(...) here, but you can't have 2 mutable references
     let mut p1_index = None;
     let mut p2_index = None;
-    for i in 0 .. circuits.len() {
-        if circuits[i].contains(p1) {
+    for (i, circuit) in circuits.iter().enumerate() {
+        if circuit.contains(p1) {
             p1_index = Some(i);
         }
-        if circuits[i].contains(p2) {
+        if circuit.contains(p2) {
             p2_index = Some(i);
         }
     }
@@ -95,38 +81,28 @@ fn add_circuit_connection(pair: &(Point3d, Point3d), circuits: &mut Vec<HashSet<
                 // then we need to combine the 2 into 1 circuit
                 // Make a new one an remove the 2 previous
                 let new_circuit = circuits[p1_circuit].union(&circuits[p2_circuit])
-                    .map(|p| p.clone())
+                    .copied()
                     .collect::<HashSet<_>>();
                 circuits.remove(p1_circuit.max(p2_circuit));
                 circuits.remove(p1_circuit.min(p2_circuit));
                 circuits.push(new_circuit);
             }
         } else {
-            circuits[p1_circuit].insert(p2.clone());
+            circuits[p1_circuit].insert(*p2);
         }
     } else if let Some(p2_circuit) = p2_index {
-        circuits[p2_circuit].insert(p1.clone());
+        circuits[p2_circuit].insert(*p1);
     }
     else {
         let mut new_circuit = HashSet::new();
-        new_circuit.insert(p1.clone());
-        new_circuit.insert(p2.clone());
+        new_circuit.insert(*p1);
+        new_circuit.insert(*p2);
         circuits.push(new_circuit);
     }
 }
 
-fn parse_input(input: &str) -> Vec<Point3d> {
+fn parse_input(input: &str) -> Vec<Point3d64> {
     input.lines()
-        .map(|line| {
-            let parts = line.trim()
-                .split(",")
-                .map(|s| s.parse().unwrap())
-                .collect::<Vec<_>>();
-            Point3d{
-                x: parts[0],
-                y: parts[1],
-                z: parts[2],
-            }
-        })
+        .map(|line| line.parse().unwrap())
         .collect()
 }
\ No newline at end of file
@@ -16,6 +16,7 @@ use day6::Day6;
 use day7::Day7;
 use day8::Day8;
 
+use crate::util::num::catch_overflow;
 use std::fmt::Display;
 use std::time::Instant;
 
@@ -28,11 +29,12 @@ trait Day<T> {
         let now = Instant::now();
         let input = Self::read_input();
         println!("Parsed input in {}ms", now.elapsed().as_nanos() as f64 / 1_000_000.0);
+        // An overflow is only caught when running with --checked, otherwise parts run as normal
         let now = Instant::now();
-        let part1 = Self::part1(&input);
+        let part1 = catch_overflow(|| Self::part1(&input).to_string()).unwrap_or_else(|e| e.to_string());
         println!("Part 1: {part1} ({}ms)", now.elapsed().as_nanos() as f64 / 1_000_000.0);
         let now = Instant::now();
-        let part2 = Self::part2(&input);
+        let part2 = catch_overflow(|| Self::part2(&input).to_string()).unwrap_or_else(|e| e.to_string());
         println!("Part 2: {part2} ({}ms)", now.elapsed().as_nanos() as f64 / 1_000_000.0);
     }
 }
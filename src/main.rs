@@ -3,6 +3,7 @@ mod day;
 pub mod util;
 
 use day::run;
+use util::num::enable_overflow_checks;
 use std::env;
 use std::process;
 fn main() {
@@ -11,9 +12,16 @@ fn main() {
         println!("Usage - list each day you want to run");
         println!("    to run days 1 and 15:");
         println!("    cargo run 1 15");
+        println!("    add --checked to report arithmetic overflow instead of a wrong answer");
         process::exit(0);
     }
-    let days = &args[1..];
+    let (flags, days): (Vec<_>, Vec<_>) = args[1..].iter().partition(|arg| arg.starts_with("--"));
+    for flag in flags {
+        match flag.as_str() {
+            "--checked" => enable_overflow_checks(),
+            _ => println!("Invalid argument: {flag}"),
+        }
+    }
     for day in days {
         if let Ok(day) = day.parse::<i32>() {
             run(day);
@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use super::bitgrid::{BitGrid, NeighborCounts};
+use super::fill::Connectivity;
+use super::vec2d::Vec2d;
+
+/// Why [`Automaton::run`] stopped
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Stop {
+    /// The last step didn't change any cells
+    FixedPoint,
+    /// The step limit was reached
+    StepLimit,
+    /// The grid after `start + length` steps is the same as after `start` steps
+    Cycle { start: usize, length: usize },
+}
+
+/// What happened during one step
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub struct StepStats {
+    /// Number of cells whose value changed
+    pub changed: usize,
+}
+
+// writes the state after one step into the second grid, returning how many cells changed
+type StepFn<'a, G> = Box<dyn FnMut(&G, &mut G) -> usize + 'a>;
+// remembers every state seen so far, returning the step a repeated state was first seen at
+type SeenStates<'a, G> = Box<dyn FnMut(&G, usize) -> Option<usize> + 'a>;
+
+/// Steps every cell of a grid at once according to a rule.
+///
+/// [`Self::new`] runs a rule on each cell of a [`Vec2d`]. The rule gets the cell and the values of its neighbors,
+/// and returns the cell's next value. Neighbors are looked up with [`Vec2d::get`], so the grid's edge policy
+/// decides what happens at the edges: bounded grids give edge cells fewer neighbors, wrapping grids behave like a torus.
+///
+/// [`Self::bits`] runs a rule on a whole [`BitGrid`] at once, which is much faster for rules on booleans
+/// that only depend on how many neighbors are set. Bit grids are always bounded.
+pub struct Automaton<'a, G> {
+    current: G,
+    // the next state is written here, then swapped with `current`
+    next: G,
+    step: StepFn<'a, G>,
+    max_steps: Option<usize>,
+    seen: Option<SeenStates<'a, G>>,
+    repeat_of: Option<usize>,
+    history: Vec<StepStats>,
+}
+
+impl<'a, T: Clone + PartialEq + 'a> Automaton<'a, Vec2d<T>> {
+
+    pub fn new(grid: Vec2d<T>, connectivity: Connectivity, mut rule: impl FnMut(&T, &[&T]) -> T + 'a) -> Self {
+        Self::with_step(grid, move |current: &Vec2d<T>, next: &mut Vec2d<T>| {
+            let mut changed = 0;
+            let mut neighbors = Vec::with_capacity(8);
+            for (idx, cell) in current.grid.iter().enumerate() {
+                let point = current.idx_to_point(idx);
+                neighbors.clear();
+                neighbors.extend(connectivity.directions().iter()
+                    .filter_map(|&direction| current.get(point + direction.to_point())));
+                let value = rule(cell, &neighbors);
+                if value != *cell {
+                    changed += 1;
+                }
+                next.grid[idx] = value;
+            }
+            changed
+        })
+    }
+}
+
+impl<'a> Automaton<'a, BitGrid> {
+
+    /// Steps a whole [`BitGrid`] at once. `rule` gets the grid and the neighbor counts of every cell,
+    /// and returns the next grid.
+    pub fn bits(grid: BitGrid, connectivity: Connectivity, mut rule: impl FnMut(&BitGrid, &NeighborCounts) -> BitGrid + 'a) -> Self {
+        Self::with_step(grid, move |current: &BitGrid, next: &mut BitGrid| {
+            *next = rule(current, &current.neighbor_counts(connectivity));
+            (current ^ &*next).count_ones()
+        })
+    }
+}
+
+impl<'a, G: Clone> Automaton<'a, G> {
+
+    fn with_step(grid: G, step: impl FnMut(&G, &mut G) -> usize + 'a) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            step: Box::new(step),
+            max_steps: None,
+            seen: None,
+            repeat_of: None,
+            history: vec![],
+        }
+    }
+
+    /// Stops [`Self::run`] once this many steps have been taken in total
+    #[must_use]
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Advances every cell by one step, returning how many cells changed
+    pub fn step(&mut self) -> usize {
+        let changed = (self.step)(&self.current, &mut self.next);
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.history.push(StepStats { changed });
+        if let Some(seen) = &mut self.seen {
+            self.repeat_of = seen(&self.current, self.history.len());
+        }
+        changed
+    }
+
+    /// Steps until the grid stops changing, the step limit is reached, or a repeated state is found.
+    /// Without a step limit or cycle detection, a grid that never settles runs forever.
+    pub fn run(&mut self) -> Stop {
+        loop {
+            if self.max_steps.is_some_and(|max_steps| self.steps() >= max_steps) {
+                return Stop::StepLimit;
+            }
+            if self.step() == 0 {
+                return Stop::FixedPoint;
+            }
+            if let Some(start) = self.repeat_of {
+                return Stop::Cycle { start, length: self.steps() - start };
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn grid(&self) -> &G {
+        &self.current
+    }
+
+    #[must_use]
+    pub fn into_grid(self) -> G {
+        self.current
+    }
+
+    /// Number of steps taken so far
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Statistics for every step taken so far, in order
+    #[must_use]
+    pub fn history(&self) -> &[StepStats] {
+        &self.history
+    }
+}
+
+impl<'a, G: Clone + Eq + Hash + 'a> Automaton<'a, G> {
+
+    /// Makes [`Self::run`] stop when the grid returns to a state it was in before.
+    /// Every state is kept from now on, so this uses memory proportional to the number of steps.
+    #[must_use]
+    pub fn detect_cycles(mut self) -> Self {
+        let mut seen = HashMap::new();
+        seen.insert(self.current.clone(), self.steps());
+        self.seen = Some(Box::new(move |grid: &G, step| {
+            match seen.get(grid) {
+                Some(&start) => Some(start),
+                None => {
+                    seen.insert(grid.clone(), step);
+                    None
+                }
+            }
+        }));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::vec2d::EdgePolicy;
+
+    fn life(alive: &bool, neighbors: &[&bool]) -> bool {
+        let count = neighbors.iter().filter(|&&&n| n).count();
+        count == 3 || (*alive && count == 2)
+    }
+
+    fn parse(text: &str) -> Vec2d<bool> {
+        Vec2d::parse(text, |c| c == '#').unwrap()
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        // a block never changes
+        let mut automaton = Automaton::new(parse("....\n.##.\n.##.\n...."), Connectivity::Eight, life);
+        assert_eq!(Stop::FixedPoint, automaton.run());
+        assert_eq!(1, automaton.steps());
+        assert_eq!(vec![StepStats { changed: 0 }], automaton.history());
+    }
+
+    #[test]
+    fn test_cycle_and_limit() {
+        // a blinker flips between horizontal and vertical
+        let blinker = parse(".....\n.....\n.###.\n.....\n.....");
+        let mut automaton = Automaton::new(blinker.clone(), Connectivity::Eight, life).detect_cycles();
+        assert_eq!(Stop::Cycle { start: 0, length: 2 }, automaton.run());
+        assert_eq!(&blinker, automaton.grid());
+        assert!(automaton.history().iter().all(|stats| stats.changed == 4));
+
+        let mut automaton = Automaton::new(blinker.clone(), Connectivity::Eight, life).max_steps(3);
+        assert_eq!(Stop::StepLimit, automaton.run());
+        assert_eq!(blinker.transpose(), automaton.into_grid());
+    }
+
+    #[test]
+    fn test_wrapping_edges() {
+        // a glider moves one cell diagonally every 4 steps, so on a 6x6 torus it's back after 24
+        let glider = parse(".#....\n..#...\n###...\n......\n......\n......").with_edges(EdgePolicy::Wrap);
+        let mut automaton = Automaton::new(glider.clone(), Connectivity::Eight, life).detect_cycles();
+        assert_eq!(Stop::Cycle { start: 0, length: 24 }, automaton.run());
+    }
+
+    #[test]
+    fn test_bits_match_cells() {
+        let glider = parse(".#....\n..#...\n###...\n......\n......\n......");
+        let mut cells = Automaton::new(glider.clone(), Connectivity::Eight, life).max_steps(10);
+        let mut bits = Automaton::bits(BitGrid::from(&glider), Connectivity::Eight, |alive, counts| {
+            &counts.exactly(3) | &(alive & &counts.exactly(2))
+        }).max_steps(10);
+        assert_eq!(cells.run(), bits.run());
+        assert_eq!(cells.history(), bits.history());
+        assert_eq!(cells.grid(), &Vec2d::from(bits.grid()));
+    }
+}
@@ -0,0 +1,350 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use super::fill::Connectivity;
+use super::point::Point;
+use super::vec2d::Vec2d;
+
+/// A grid of booleans packed 64 to a word, with each row starting on a new word.
+/// Bit `x % 64` of word `x / 64` in a row holds column `x`.
+///
+/// Operations work on whole words at once, so whole-grid steps like counting every cell's
+/// neighbors are much faster than visiting each cell of a `Vec2d<bool>`.
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub struct BitGrid {
+    width: i32,
+    height: i32,
+    words_per_row: usize,
+    // bits past the width of each row are always 0
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+
+    /// A grid with every cell false
+    ///
+    /// # Panics
+    /// If `width` or `height` is not positive
+    #[must_use]
+    pub fn new(width: i32, height: i32) -> Self {
+        assert!(width > 0 && height > 0, "Invalid size {width}x{height}");
+        let words_per_row = (width as usize).div_ceil(64);
+        Self { width, height, words_per_row, words: vec![0; words_per_row * height as usize] }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    #[must_use]
+    pub fn in_bounds(&self, point: Point) -> bool {
+        point.x >= 0 && point.y >= 0 && point.x < self.width && point.y < self.height
+    }
+
+    /// The word holding `point` and the bit for it within that word
+    fn locate(&self, point: Point) -> (usize, u64) {
+        assert!(self.in_bounds(point), "{point:?} is outside the grid");
+        let word = point.y as usize * self.words_per_row + point.x as usize / 64;
+        (word, 1 << (point.x % 64))
+    }
+
+    /// # Panics
+    /// If `point` is outside the grid
+    #[must_use]
+    pub fn get(&self, point: Point) -> bool {
+        let (word, bit) = self.locate(point);
+        self.words[word] & bit != 0
+    }
+
+    /// # Panics
+    /// If `point` is outside the grid
+    pub fn set(&mut self, point: Point, value: bool) {
+        let (word, bit) = self.locate(point);
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+    }
+
+    /// Number of true cells
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Every true cell in reading order
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        self.words.iter().enumerate().flat_map(move |(idx, &word)| {
+            let y = (idx / self.words_per_row) as i32;
+            let x_offset = (idx % self.words_per_row) as i32 * 64;
+            let mut remaining = word;
+            std::iter::from_fn(move || {
+                (remaining != 0).then(|| {
+                    let bit = remaining.trailing_zeros() as i32;
+                    remaining &= remaining - 1;
+                    Point::new(x_offset + bit, y)
+                })
+            })
+        })
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row .. (y + 1) * self.words_per_row]
+    }
+
+    /// Clears the bits past the width of each row
+    fn mask_rows(&mut self) {
+        let extra = self.words_per_row * 64 - self.width as usize;
+        if extra > 0 {
+            let mask = u64::MAX >> extra;
+            for word in self.words.iter_mut().skip(self.words_per_row - 1).step_by(self.words_per_row) {
+                *word &= mask;
+            }
+        }
+    }
+
+    /// Moves every cell `dx` columns right and `dy` rows down. Negative values move left and up.
+    /// Cells moved off the grid are lost and the cells left behind are false.
+    #[must_use]
+    pub fn shift(&self, dx: i32, dy: i32) -> BitGrid {
+        let mut shifted = BitGrid::new(self.width, self.height);
+        if dx.abs() >= self.width || dy.abs() >= self.height {
+            return shifted;
+        }
+        let words = self.words_per_row as isize;
+        let (word_shift, bit_shift) = ((dx / 64) as isize, dx.unsigned_abs() % 64);
+        for y in 0 .. self.height {
+            let source_y = y - dy;
+            if source_y < 0 || source_y >= self.height {
+                continue;
+            }
+            let source = self.row(source_y as usize);
+            let target = &mut shifted.words[y as usize * self.words_per_row ..][.. self.words_per_row];
+            // a word in the target row is built from up to two neighboring words of the source row
+            let word_at = |i: isize| if (0 .. words).contains(&i) { source[i as usize] } else { 0 };
+            for (i, word) in target.iter_mut().enumerate() {
+                let i = i as isize - word_shift;
+                *word = if bit_shift == 0 {
+                    word_at(i)
+                } else if dx > 0 {
+                    word_at(i) << bit_shift | word_at(i - 1) >> (64 - bit_shift)
+                } else {
+                    word_at(i) >> bit_shift | word_at(i + 1) << (64 - bit_shift)
+                };
+            }
+        }
+        shifted.mask_rows();
+        shifted
+    }
+
+    /// How many true neighbors every cell has, counted for the whole grid at once
+    #[must_use]
+    pub fn neighbor_counts(&self, connectivity: Connectivity) -> NeighborCounts {
+        // each count is stored as 4 bit planes, and each neighbor grid is added in with a ripple carry
+        let mut planes: [BitGrid; 4] = std::array::from_fn(|_| BitGrid::new(self.width, self.height));
+        for &direction in connectivity.directions() {
+            let offset = direction.to_point();
+            let mut carry = self.shift(-offset.x, -offset.y);
+            for plane in &mut planes {
+                let next_carry = &*plane & &carry;
+                *plane ^= &carry;
+                carry = next_carry;
+            }
+        }
+        NeighborCounts { planes }
+    }
+
+    /// Clears every cell that is true in `mask`
+    ///
+    /// # Panics
+    /// If the grids are different sizes
+    pub fn remove(&mut self, mask: &BitGrid) {
+        self.zip_words(mask, |word, other| *word &= !other);
+    }
+
+    /// The cells that are true here but not in `mask`
+    ///
+    /// # Panics
+    /// If the grids are different sizes
+    #[must_use]
+    pub fn and_not(&self, mask: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.remove(mask);
+        result
+    }
+
+    fn zip_words(&mut self, other: &BitGrid, op: impl Fn(&mut u64, u64)) {
+        assert!(self.width == other.width && self.height == other.height, "Grids are different sizes");
+        self.words.iter_mut().zip(&other.words).for_each(|(word, &other)| op(word, other));
+    }
+}
+
+/// The number of true neighbors of every cell in a [`BitGrid`], stored one bit of the count per grid
+pub struct NeighborCounts {
+    planes: [BitGrid; 4],
+}
+
+impl NeighborCounts {
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> u32 {
+        self.planes.iter().enumerate()
+            .map(|(place, plane)| u32::from(plane.get(point)) << place)
+            .sum()
+    }
+
+    /// The cells with exactly `count` true neighbors
+    #[must_use]
+    pub fn exactly(&self, count: u32) -> BitGrid {
+        let mut matches = !&BitGrid::new(self.planes[0].width, self.planes[0].height);
+        for (place, plane) in self.planes.iter().enumerate() {
+            if count >> place & 1 == 1 {
+                matches &= plane;
+            } else {
+                matches &= &!plane;
+            }
+        }
+        matches
+    }
+
+    /// The cells with `count` or more true neighbors
+    #[must_use]
+    pub fn at_least(&self, count: u32) -> BitGrid {
+        let mut matches = BitGrid::new(self.planes[0].width, self.planes[0].height);
+        for count in count ..= 8 {
+            matches |= &self.exactly(count);
+        }
+        matches
+    }
+}
+
+impl From<&Vec2d<bool>> for BitGrid {
+    fn from(grid: &Vec2d<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.width(), grid.height());
+        for (point, &value) in grid.enumerate() {
+            if value {
+                bits.set(point, true);
+            }
+        }
+        bits
+    }
+}
+
+impl From<&BitGrid> for Vec2d<bool> {
+    fn from(bits: &BitGrid) -> Self {
+        let mut grid = Vec2d::filled(bits.width, bits.height, false);
+        for point in bits.positions() {
+            grid[point] = true;
+        }
+        grid
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut inverted = self.clone();
+        inverted.words.iter_mut().for_each(|word| *word = !*word);
+        inverted.mask_rows();
+        inverted
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident, $symbol:tt) => {
+        impl $assign<&BitGrid> for BitGrid {
+            /// # Panics
+            /// If the grids are different sizes
+            fn $assign_method(&mut self, rhs: &BitGrid) {
+                self.zip_words(rhs, |word, other| *word $symbol other);
+            }
+        }
+
+        impl $op for &BitGrid {
+            type Output = BitGrid;
+
+            /// # Panics
+            /// If the grids are different sizes
+            fn $method(self, rhs: &BitGrid) -> BitGrid {
+                let mut result = self.clone();
+                result.$assign_method(rhs);
+                result
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &=);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |=);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^=);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(text: &str) -> BitGrid {
+        BitGrid::from(&Vec2d::parse(text, |c| c == '#').unwrap())
+    }
+
+    #[test]
+    fn test_round_trip_and_ops() {
+        let a = bits("##..\n.#.#");
+        let b = bits("#.#.\n...#");
+        assert_eq!(Vec2d::parse("##..\n.#.#", |c| c == '#').unwrap(), Vec2d::from(&a));
+        assert_eq!(bits("#...\n...#"), &a & &b);
+        assert_eq!(bits("###.\n.#.#"), &a | &b);
+        assert_eq!(bits(".##.\n.#.."), &a ^ &b);
+        assert_eq!(bits("..##\n#.#."), !&a);
+        assert_eq!(bits(".#..\n.#.."), a.and_not(&b));
+        let mut removed = a.clone();
+        removed.remove(&b);
+        assert_eq!(&a & &!&b, removed);
+        assert_eq!(4, a.count_ones());
+        assert_eq!(vec![Point::new(0, 0), Point::new(1, 0), Point::new(1, 1), Point::new(3, 1)], a.positions().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shift_across_words() {
+        // 130 columns spans 3 words per row
+        let mut grid = BitGrid::new(130, 3);
+        for x in [0, 63, 64, 127, 129] {
+            grid.set(Point::new(x, 1), true);
+        }
+        for (dx, dy) in [(1, 0), (-1, 0), (65, 1), (-64, -1), (0, 0), (129, 0), (-130, 0)] {
+            let shifted = grid.shift(dx, dy);
+            let expected = grid.positions()
+                .map(|p| Point::new(p.x + dx, p.y + dy))
+                .filter(|&p| shifted.in_bounds(p))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, shifted.positions().collect::<Vec<_>>(), "shift {dx},{dy}");
+        }
+    }
+
+    #[test]
+    fn test_neighbor_counts() {
+        let text = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@";
+        let grid = Vec2d::parse(text, |c| c == '@').unwrap();
+        let counts = BitGrid::from(&grid).neighbor_counts(Connectivity::Eight);
+        for (point, _) in grid.enumerate() {
+            let expected = grid.neighbors8(point).filter(|&(_, _, &paper)| paper).count();
+            assert_eq!(expected as u32, counts.get(point), "{point:?}");
+            assert!(counts.exactly(expected as u32).get(point));
+            assert_eq!(expected >= 4, counts.at_least(4).get(point));
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use super::point::{Point, Point64};
+use super::rect::Rect;
+use super::vec2d::Vec2d;
+
+/// One axis of a compressed grid.
+///
+/// Every distinct value gets a cell of width 1, and each gap between two consecutive values
+/// is collapsed into a single cell as wide as the gap. That keeps the shape of regions intact
+/// while letting each compressed cell remember how much real space it stands for.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct CompressedAxis {
+    starts: Vec<i64>,
+    widths: Vec<i64>,
+}
+
+impl CompressedAxis {
+
+    /// # Panics
+    /// If a gap between two consecutive values is wider than `i64::MAX`
+    #[must_use]
+    pub fn new(values: impl IntoIterator<Item = i64>) -> Self {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+        values.sort_unstable();
+        values.dedup();
+        let mut starts = vec![];
+        let mut widths = vec![];
+        for (i, &value) in values.iter().enumerate() {
+            // compared without adding, so `i64::MAX` can be one of the values
+            if i > 0 && value.abs_diff(values[i - 1]) > 1 {
+                let previous = values[i - 1];
+                starts.push(previous + 1);
+                widths.push((value - 1).checked_sub(previous).expect("Gap is too wide for an i64"));
+            }
+            starts.push(value);
+            widths.push(1);
+        }
+        Self { starts, widths }
+    }
+
+    /// The number of compressed cells
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// The compressed cell covering `value`, or `None` if it is outside the axis
+    #[must_use]
+    pub fn index_of(&self, value: i64) -> Option<usize> {
+        let idx = self.starts.partition_point(|&start| start <= value).checked_sub(1)?;
+        (value.abs_diff(self.starts[idx]) < self.widths[idx].unsigned_abs()).then_some(idx)
+    }
+
+    /// The first real value covered by a compressed cell
+    #[must_use]
+    pub fn start(&self, idx: usize) -> i64 {
+        self.starts[idx]
+    }
+
+    /// The number of real values covered by a compressed cell
+    #[must_use]
+    pub fn width(&self, idx: usize) -> i64 {
+        self.widths[idx]
+    }
+
+    /// The real distance between the starts of two compressed cells
+    #[must_use]
+    pub fn distance(&self, a: usize, b: usize) -> u64 {
+        self.starts[b].abs_diff(self.starts[a])
+    }
+}
+
+/// Maps points with huge, sparse coordinates onto a small dense grid.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct CoordinateCompression {
+    pub x: CompressedAxis,
+    pub y: CompressedAxis,
+}
+
+impl CoordinateCompression {
+
+    /// Compresses around the given points. Add points one past the extremes
+    /// if you need a border of empty cells around everything.
+    #[must_use]
+    pub fn new(points: &[Point64]) -> Self {
+        Self {
+            x: CompressedAxis::new(points.iter().map(|p| p.x)),
+            y: CompressedAxis::new(points.iter().map(|p| p.y)),
+        }
+    }
+
+    /// The compressed point for a real point, or `None` if it is outside the compressed area
+    ///
+    /// # Panics
+    /// If there are more compressed cells than fit in an `i32`
+    #[must_use]
+    pub fn compress(&self, point: Point64) -> Option<Point> {
+        let x = self.x.index_of(point.x)?;
+        let y = self.y.index_of(point.y)?;
+        Some(Point::new(x.try_into().expect("Invalid x index"), y.try_into().expect("Invalid y index")))
+    }
+
+    /// The real points covered by a compressed point
+    ///
+    /// # Panics
+    /// If the point is not inside the compressed grid
+    #[must_use]
+    pub fn cell_rect(&self, point: Point) -> Rect<i64> {
+        let (x, y) = Self::indexes(point);
+        let min = Point64::new(self.x.start(x), self.y.start(y));
+        let max = Point64::new(min.x + (self.x.width(x) - 1), min.y + (self.y.width(y) - 1));
+        Rect { min, max }
+    }
+
+    /// The number of real points covered by a compressed point.
+    /// Widths are at most `i64::MAX`, so the product always fits in a `u128`.
+    ///
+    /// # Panics
+    /// If the point is not inside the compressed grid
+    #[must_use]
+    pub fn cell_area(&self, point: Point) -> u128 {
+        let (x, y) = Self::indexes(point);
+        u128::from(self.x.width(x).unsigned_abs()) * u128::from(self.y.width(y).unsigned_abs())
+    }
+
+    /// The real Manhattan distance between the starts of two compressed cells
+    ///
+    /// # Panics
+    /// If either point is not inside the compressed grid
+    #[must_use]
+    pub fn distance(&self, a: Point, b: Point) -> u128 {
+        let (ax, ay) = Self::indexes(a);
+        let (bx, by) = Self::indexes(b);
+        u128::from(self.x.distance(ax, bx)) + u128::from(self.y.distance(ay, by))
+    }
+
+    /// Builds the compressed grid, using the real area each cell covers to pick its value.
+    /// Returns `None` if the compression was built from no points, since a grid can't be empty.
+    ///
+    /// # Panics
+    /// If there are more compressed cells than fit in an `i32`
+    #[must_use]
+    pub fn to_vec2d<T: Clone>(&self, mut value: impl FnMut(Rect<i64>) -> T) -> Option<Vec2d<T>> {
+        if self.x.is_empty() {
+            return None;
+        }
+        let line_len = self.x.len().try_into().expect("Too many compressed columns");
+        let mut grid = Vec::with_capacity(self.x.len() * self.y.len());
+        for y in 0 .. self.y.len() {
+            for x in 0 .. self.x.len() {
+                grid.push(value(self.cell_rect(Point::new(x as i32, y as i32))));
+            }
+        }
+        Some(Vec2d::new(grid, line_len))
+    }
+
+    /// The total real area of every compressed cell that matches the predicate
+    ///
+    /// # Panics
+    /// If the area is more than `u128::MAX`, which only happens when the matching cells cover every `Point64`
+    pub fn area<T: Clone>(&self, grid: &Vec2d<T>, predicate: impl Fn(&T) -> bool) -> u128 {
+        grid.grid.iter().enumerate()
+            .filter(|(_, cell)| predicate(cell))
+            .map(|(idx, _)| self.cell_area(grid.idx_to_point(idx)))
+            .try_fold(0_u128, u128::checked_add)
+            .expect("Area is too large for a u128")
+    }
+
+    fn indexes(point: Point) -> (usize, usize) {
+        (point.x.try_into().expect("Invalid x index"), point.y.try_into().expect("Invalid y index"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis() {
+        let axis = CompressedAxis::new([1_000_000_000, 5, 7, 6, 5]);
+        // 5, 6, 7, gap of 8 ..= 999_999_999, 1_000_000_000
+        assert_eq!(5, axis.len());
+        assert_eq!(Some(0), axis.index_of(5));
+        assert_eq!(Some(3), axis.index_of(123_456));
+        assert_eq!(Some(4), axis.index_of(1_000_000_000));
+        assert_eq!(None, axis.index_of(4));
+        assert_eq!(None, axis.index_of(1_000_000_001));
+        assert_eq!(999_999_992, axis.width(3));
+        assert_eq!(999_999_995, axis.distance(0, 4));
+
+        let extremes = CompressedAxis::new([i64::MAX, i64::MAX - 3, 0]);
+        assert_eq!(Some(2), extremes.index_of(i64::MAX - 3));
+        assert_eq!(Some(3), extremes.index_of(i64::MAX - 1));
+        assert_eq!(Some(4), extremes.index_of(i64::MAX));
+
+        let full = CompressedAxis::new([i64::MIN, -1, i64::MAX]);
+        assert_eq!(u64::MAX, full.distance(4, 0));
+    }
+
+    #[test]
+    fn test_compressed_area() {
+        // two real rectangles: (0,0)-(2_000_000_000,10) and (0,0)-(10,3_000_000_000)
+        let corners = [
+            Point64::new(0, 0),
+            Point64::new(2_000_000_000, 10),
+            Point64::new(10, 3_000_000_000),
+        ];
+        let compression = CoordinateCompression::new(&corners);
+        let grid = compression.to_vec2d(|cell| {
+            let in_wide = cell.max.x <= 2_000_000_000 && cell.max.y <= 10;
+            let in_tall = cell.max.x <= 10 && cell.max.y <= 3_000_000_000;
+            in_wide || in_tall
+        }).unwrap();
+        assert_eq!(5 * 5, grid.grid.len());
+        let expected = 2_000_000_001 * 11 + 11 * 3_000_000_001 - 11 * 11;
+        assert_eq!(expected, compression.area(&grid, |&filled| filled));
+
+        let a = compression.compress(Point64::new(0, 0)).unwrap();
+        let b = compression.compress(Point64::new(2_000_000_000, 3_000_000_000)).unwrap();
+        assert_eq!(Point::new(4, 4), b);
+        assert_eq!(5_000_000_000, compression.distance(a, b));
+
+        assert_eq!(None, CoordinateCompression::new(&[]).to_vec2d(|_| false));
+    }
+
+    #[test]
+    fn test_extreme_area() {
+        // both gaps are as wide as an i64 can hold
+        let corners = [Point64::new(i64::MIN, i64::MIN), Point64::new(-1, -1), Point64::new(i64::MAX, i64::MAX)];
+        let compression = CoordinateCompression::new(&corners);
+        let grid = compression.to_vec2d(|cell| cell.min.x >= 0 && cell.min.y >= 0).unwrap();
+        // every point with both coordinates at least 0
+        assert_eq!(1 << 126, compression.area(&grid, |&filled| filled));
+
+        let (min, max) = (compression.compress(corners[0]).unwrap(), compression.compress(corners[2]).unwrap());
+        assert_eq!(2 * u128::from(u64::MAX), compression.distance(min, max));
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A sequence of states that eventually repeats: the state after `start + length` steps
+/// is the same as the state after `start` steps, and so on forever
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub struct Cycle {
+    /// Number of steps before the first state that repeats
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+
+    /// The earliest step with the same state as step `n`
+    #[must_use]
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The state after `n` steps, found by replaying the steps to the earliest equivalent one
+    pub fn state_at<S>(&self, initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+        (0 .. self.equivalent_step(n)).fold(initial, |state, _| step(&state))
+    }
+}
+
+/// Floyd's tortoise and hare. Only ever holds 2 states, at the cost of calling `step` around 3 times as often
+/// as [`find_cycle`]. Never returns if the states don't repeat.
+pub fn floyd<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // the hare moves twice as fast, so they meet somewhere in the cycle
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let hare_once = step(&hare);
+        hare = step(&hare_once);
+    }
+
+    // the distance to the start of the cycle from the beginning and from the meeting point is the same
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Brent's algorithm. Only ever holds 2 states like [`floyd`], but usually calls `step` fewer times.
+/// Never returns if the states don't repeat.
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // the tortoise teleports to the hare at every power of 2 until the hare comes back around to it
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // with the hare a full cycle ahead, they meet at the start of the cycle
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = (0 .. length).fold(initial, |state, _| step(&state));
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Remembers every state until one repeats. Calls `step` the fewest times, but keeps every state.
+/// Never returns if the states don't repeat.
+pub fn find_cycle<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S) -> Cycle {
+    run_until_cycle(initial, step, |_| (), None).0
+        .expect("States are only recorded until they cycle")
+}
+
+/// Steps through the states, recording `value` for each until either a state repeats or step `limit` is reached
+fn run_until_cycle<S: Clone + Eq + Hash, V>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    mut value: impl FnMut(&S) -> V,
+    limit: Option<usize>,
+) -> (Option<Cycle>, Vec<V>) {
+    let mut seen = HashMap::new();
+    let mut values = vec![];
+    let mut state = initial;
+    loop {
+        let n = values.len();
+        if let Some(&start) = seen.get(&state) {
+            return (Some(Cycle { start, length: n - start }), values);
+        }
+        values.push(value(&state));
+        if limit == Some(n) {
+            return (None, values);
+        }
+        let next = step(&state);
+        seen.insert(state, n);
+        state = next;
+    }
+}
+
+/// The state after `n` steps, skipping ahead once the states start repeating.
+/// Every state up to the first repeat is kept, so [`value_at`] uses less memory when only part of a state is needed.
+pub fn state_at<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    value_at(initial, step, S::clone, n)
+}
+
+/// A value derived from the state after `n` steps, such as a score or a count,
+/// skipping ahead once the states start repeating
+pub fn value_at<S: Clone + Eq + Hash, V>(initial: S, step: impl FnMut(&S) -> S, value: impl FnMut(&S) -> V, n: usize) -> V {
+    let (cycle, mut values) = run_until_cycle(initial, step, value, Some(n));
+    let n = cycle.map_or(n, |cycle| cycle.equivalent_step(n));
+    values.swap_remove(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::vec2d::Vec2d;
+
+    fn next(x: &u64) -> u64 {
+        (x * x + 1) % 1_000
+    }
+
+    #[test]
+    fn test_finders_agree() {
+        for initial in [0, 3, 17, 500, 999] {
+            let cycle = find_cycle(initial, next);
+            assert_eq!(cycle, floyd(initial, next));
+            assert_eq!(cycle, brent(initial, next));
+            let at = |steps: usize| (0 .. steps).fold(initial, |x, _| next(&x));
+            assert_eq!(at(cycle.start), at(cycle.start + cycle.length));
+            assert!(cycle.start == 0 || at(cycle.start - 1) != at(cycle.start - 1 + cycle.length));
+        }
+    }
+
+    #[test]
+    fn test_jump_ahead() {
+        let initial = 17;
+        let direct = (0 .. 12_345).fold(initial, |x, _| next(&x));
+        assert_eq!(direct, state_at(initial, next, 12_345));
+        assert_eq!(direct % 7, value_at(initial, next, |x| x % 7, 12_345));
+        assert_eq!(direct, brent(initial, next).state_at(initial, next, 12_345));
+        // before any state repeats
+        assert_eq!(next(&next(&initial)), state_at(initial, next, 2));
+    }
+
+    #[test]
+    fn test_grid_states() {
+        // rotating a grid comes back around after 4 quarter turns
+        let grid = Vec2d::parse("ab\ncd", |c| c).unwrap();
+        assert_eq!(Cycle { start: 0, length: 4 }, find_cycle(grid.clone(), Vec2d::rotate_cw));
+        assert_eq!(grid.rotate_ccw(), state_at(grid.clone(), Vec2d::rotate_cw, 1_000_000_000_003));
+        assert_eq!('c', value_at(grid, Vec2d::rotate_cw, |grid| grid.grid[0], 1_000_000_000_001));
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::VecDeque;
+use super::grid::Grid;
+use super::point::Point;
+use super::rect::Rect;
+use super::vec2d::Directions;
+
+/// Which cells count as touching
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Connectivity {
+    /// Up, down, left and right
+    Four,
+    /// Diagonals as well
+    Eight,
+}
+
+impl Connectivity {
+    #[must_use]
+    pub fn directions(self) -> &'static [Directions] {
+        match self {
+            Connectivity::Four => &Directions::CARDINAL,
+            Connectivity::Eight => &Directions::ALL,
+        }
+    }
+}
+
+/// A group of connected cells found by [`GridFill::label_regions`]
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub struct Region {
+    /// Index of this region, and its value in [`Regions::labels`]
+    pub label: usize,
+    /// The first cell of the region in reading order
+    pub seed: Point,
+    /// Number of cells
+    pub size: usize,
+    pub bounds: Rect,
+    /// Number of cell edges that border a different region or the outside of the grid
+    pub perimeter: usize,
+    /// Number of straight sides along the perimeter, counting the edges around any holes
+    pub sides: usize,
+}
+
+/// Every region in a grid, and which region each cell belongs to.
+/// `labels` is the same kind of grid that was labelled.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Regions<L> {
+    pub labels: L,
+    pub regions: Vec<Region>,
+}
+
+impl<L: Grid<Cell = usize>> Regions<L> {
+
+    /// The region `point` belongs to, or `None` if it is outside the labelled grid
+    #[must_use]
+    pub fn region_at(&self, point: Point) -> Option<&Region> {
+        if !self.labels.in_bounds(point) {
+            return None;
+        }
+        self.labels.get(point).map(|&label| &self.regions[label])
+    }
+}
+
+/// Breadth first traversal from every seed at once, calling `visit` with each point reached
+/// and its distance from the nearest seed. `can_step(from, to)` decides whether the traversal
+/// spreads between two neighboring cells. Points already marked in `visited` are skipped,
+/// and the traversal never leaves the grid's bounds.
+fn traverse<G: Grid + ?Sized>(
+    grid: &G,
+    seeds: impl IntoIterator<Item = Point>,
+    connectivity: Connectivity,
+    visited: &mut G::Mapped<bool>,
+    mut can_step: impl FnMut(Point, Point) -> bool,
+    mut visit: impl FnMut(Point, usize),
+) {
+    let mut queue = VecDeque::new();
+    for seed in seeds {
+        if grid.in_bounds(seed) && visited.get(seed) == Some(&false) {
+            visited.set(seed, true);
+            queue.push_back((seed, 0));
+        }
+    }
+    while let Some((point, distance)) = queue.pop_front() {
+        visit(point, distance);
+        for (_, next, _) in grid.neighbors(point, connectivity.directions()) {
+            if grid.in_bounds(next) && visited.get(next) == Some(&false) && can_step(point, next) {
+                visited.set(next, true);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+}
+
+/// Fills and labels connected cells on any [`Grid`]. Only cells inside the grid's bounds are visited,
+/// so fills on a [`SparseGrid`] stop at the edge of the cells that have been set.
+///
+/// [`SparseGrid`]: super::sparse::SparseGrid
+pub trait GridFill: Grid {
+
+    /// All points connected to `seed` through cells that match `include`, in the order they were reached.
+    /// Empty if `seed` is outside the grid or doesn't match.
+    fn flood_fill(&self, seed: Point, connectivity: Connectivity, include: impl Fn(&Self::Cell) -> bool) -> Vec<Point> {
+        let mut filled = vec![];
+        if !self.in_bounds(seed) || !self.get(seed).is_some_and(&include) {
+            return filled;
+        }
+        let mut visited = self.map(|_| false);
+        let included = |_, to| self.get(to).is_some_and(&include);
+        traverse(self, [seed], connectivity, &mut visited, included, |point, _| filled.push(point));
+        filled
+    }
+
+    /// Distance from each cell to the nearest source, moving only through cells that match `passable`.
+    /// Cells that can't be reached are `None`.
+    fn distance_field(
+        &self,
+        sources: impl IntoIterator<Item = Point>,
+        connectivity: Connectivity,
+        passable: impl Fn(&Self::Cell) -> bool,
+    ) -> Self::Mapped<Option<usize>> {
+        let mut distances = self.map(|_| None);
+        let mut visited = self.map(|_| false);
+        let passable = |_, to| self.get(to).is_some_and(&passable);
+        traverse(self, sources, connectivity, &mut visited, passable, |point, distance| {
+            distances.set(point, Some(distance));
+        });
+        distances
+    }
+
+    /// Splits the grid into regions of connected cells that hold the same value.
+    /// Regions are labelled from 0 in the reading order of their first cell.
+    /// Neighbors past the edge are found with [`Grid::resolve`], so regions on a wrapping grid continue
+    /// across the edge. A region that wraps all the way around has straight edges with no corners,
+    /// and those don't count towards its sides.
+    fn label_regions(&self, connectivity: Connectivity) -> Regions<Self::Mapped<usize>>
+        where Self::Cell: PartialEq
+    {
+        let mut labels = self.map(|_| 0);
+        let mut visited = self.map(|_| false);
+        let mut regions = vec![];
+        for seed in self.bounds().points() {
+            if visited.get(seed) == Some(&true) {
+                continue;
+            }
+            let label = regions.len();
+            let mut region = Region { label, seed, size: 0, bounds: Rect::from_corners(seed, seed), perimeter: 0, sides: 0 };
+            traverse(self, [seed], connectivity, &mut visited, |from, to| self.get(from) == self.get(to), |point, _| {
+                labels.set(point, label);
+                region.size += 1;
+                region.bounds.include(point);
+            });
+            regions.push(region);
+        }
+
+        // perimeter and sides depend on the labels of neighboring cells, so count them once every cell is labelled
+        let same = |point: Point, label: usize| labels.resolve(point).is_some_and(|point| labels.get(point) == Some(&label));
+        for point in self.bounds().points() {
+            let label = labels.get(point).copied().unwrap_or_default();
+            let region = &mut regions[label];
+            for direction in Directions::CARDINAL {
+                if !same(point + direction.to_point(), label) {
+                    region.perimeter += 1;
+                }
+            }
+            // a region has as many sides as corners, and each cell can hold up to 4 of them
+            for (a, b) in [(0, -1), (1, 0), (0, 1), (-1, 0)].into_iter().zip([(1, 0), (0, 1), (-1, 0), (0, -1)]) {
+                let (a, b) = (Point::new(a.0, a.1), Point::new(b.0, b.1));
+                let (side_a, side_b, diagonal) = (same(point + a, label), same(point + b, label), same(point + a + b, label));
+                if (!side_a && !side_b) || (side_a && side_b && !diagonal) {
+                    region.sides += 1;
+                }
+            }
+        }
+        Regions { labels, regions }
+    }
+}
+
+impl<G: Grid> GridFill for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::sparse::SparseGrid;
+    use crate::util::vec2d::{EdgePolicy, Vec2d};
+
+    const GARDEN: &str = "AAAA
+BBCD
+BBCC
+EEEC";
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = Vec2d::parse(GARDEN, |c| c).unwrap();
+        let filled = grid.flood_fill(Point::new(2, 1), Connectivity::Four, |&c| c == 'C');
+        assert_eq!(4, filled.len());
+        assert_eq!(Point::new(2, 1), filled[0]);
+        assert_eq!(5, grid.flood_fill(Point::new(2, 1), Connectivity::Four, |&c| c == 'C' || c == 'D').len());
+
+        // cells that only touch at a corner are connected with 8-connectivity
+        let diagonal = Vec2d::parse("#.\n.#", |c| c).unwrap();
+        assert_eq!(1, diagonal.flood_fill(Point::new(0, 0), Connectivity::Four, |&c| c == '#').len());
+        assert_eq!(2, diagonal.flood_fill(Point::new(0, 0), Connectivity::Eight, |&c| c == '#').len());
+        assert!(grid.flood_fill(Point::new(0, 0), Connectivity::Four, |&c| c == 'C').is_empty());
+    }
+
+    #[test]
+    fn test_label_regions() {
+        let grid = Vec2d::parse(GARDEN, |c| c).unwrap();
+        let regions = grid.label_regions(Connectivity::Four);
+        let summary = regions.regions.iter()
+            .map(|r| (grid[r.seed], r.size, r.perimeter, r.sides))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![('A', 4, 10, 4), ('B', 4, 8, 4), ('C', 4, 10, 8), ('D', 1, 4, 4), ('E', 3, 8, 4)], summary);
+        assert_eq!(2, regions.labels[Point::new(3, 3)]);
+        assert_eq!(Rect::from_corners(Point::new(2, 1), Point::new(3, 3)), regions.region_at(Point::new(3, 2)).unwrap().bounds);
+    }
+
+    #[test]
+    fn test_holes() {
+        // the X regions are each 1 cell with 4 sides, and the O region has 4 sides around the outside and 4 around each hole
+        let grid = Vec2d::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO", |c| c).unwrap();
+        let regions = grid.label_regions(Connectivity::Four);
+        assert_eq!(5, regions.regions.len());
+        let outer = &regions.regions[0];
+        assert_eq!((21, 36, 20), (outer.size, outer.perimeter, outer.sides));
+    }
+
+    #[test]
+    fn test_wrapping_regions() {
+        // the four corners are one 2x2 square once the grid wraps
+        let grid = Vec2d::parse("A..A\n....\nA..A", |c| c).unwrap();
+        assert_eq!(5, grid.label_regions(Connectivity::Four).regions.len());
+        let wrapping = grid.with_edges(EdgePolicy::Wrap);
+        let regions = wrapping.label_regions(Connectivity::Four);
+        let summary = regions.regions.iter()
+            .map(|r| (wrapping[r.seed], r.size, r.perimeter, r.sides))
+            .collect::<Vec<_>>();
+        // the rest of the grid has no outside edge, only the 4 sides around the square
+        assert_eq!(vec![('A', 4, 8, 4), ('.', 8, 8, 4)], summary);
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        // a ring of walls around an unset cell at the origin
+        let ring = Rect::from_corners(Point::new(-1, -1), Point::new(1, 1)).points()
+            .filter(|&point| point != Point::new(0, 0))
+            .map(|point| (point, true))
+            .collect::<SparseGrid<bool>>();
+        let regions = ring.label_regions(Connectivity::Four);
+        assert_eq!(2, regions.regions.len());
+        let inside = regions.region_at(Point::new(0, 0)).unwrap();
+        assert_eq!((1, 4, 4), (inside.size, inside.perimeter, inside.sides));
+        assert_eq!(None, regions.region_at(Point::new(5, 5)));
+        assert_eq!(1, ring.flood_fill(Point::new(0, 0), Connectivity::Eight, |&wall| !wall).len());
+    }
+
+    #[test]
+    fn test_distance_field() {
+        let grid = Vec2d::parse("..#.\n..#.\n....", |c| c).unwrap();
+        let distances = grid.distance_field([Point::new(0, 0), Point::new(3, 0)], Connectivity::Four, |&c| c != '#');
+        assert_eq!(Some(0), distances[Point::new(3, 0)]);
+        assert_eq!(Some(2), distances[Point::new(1, 1)]);
+        assert_eq!(Some(3), distances[Point::new(2, 2)]);
+        assert_eq!(None, distances[Point::new(2, 0)]);
+    }
+}
@@ -0,0 +1,308 @@
+use std::cmp::Ordering;
+use super::point::Point64;
+
+// All of the geometry here follows the grid convention that y points down,
+// so "clockwise" means clockwise as the points would be drawn on the screen.
+// Orientation tests are exact for every Point64: the two halves of the cross product are compared
+// as 128 bit magnitudes instead of being subtracted. Areas are summed as i128 and are exact
+// for coordinates within ±2^61. Past that they panic instead of wrapping.
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Collinear,
+}
+
+/// Which way you turn walking from `a` to `b` and then on to `c`
+#[must_use]
+pub fn orientation(a: Point64, b: Point64, c: Point64) -> Orientation {
+    let ((abx, aby), (acx, acy)) = (delta(a, b), delta(a, c));
+    // the sign of the cross product abx * acy - aby * acx
+    match compare_products(abx, acy, aby, acx) {
+        Ordering::Greater => Orientation::Clockwise,
+        Ordering::Less => Orientation::CounterClockwise,
+        Ordering::Equal => Orientation::Collinear,
+    }
+}
+
+/// The vector from `a` to `b`. Always fits, since each component is within ±(2^64 - 1).
+fn delta(a: Point64, b: Point64) -> (i128, i128) {
+    (i128::from(b.x) - i128::from(a.x), i128::from(b.y) - i128::from(a.y))
+}
+
+/// Compares `a * b` with `c * d` without overflowing, for factors within ±(2^64 - 1)
+fn compare_products(a: i128, b: i128, c: i128, d: i128) -> Ordering {
+    // (is negative, magnitude), where the magnitude of two such factors always fits in a u128
+    let product = |x: i128, y: i128| {
+        let magnitude = x.unsigned_abs() * y.unsigned_abs();
+        ((x < 0) != (y < 0) && magnitude != 0, magnitude)
+    };
+    match (product(a, b), product(c, d)) {
+        ((false, left), (false, right)) => left.cmp(&right),
+        ((true, left), (true, right)) => right.cmp(&left),
+        ((left_negative, _), _) => if left_negative { Ordering::Less } else { Ordering::Greater },
+    }
+}
+
+/// Cross product of the position vectors of `a` and `b`, or `None` if it doesn't fit in an `i128`
+fn cross(a: Point64, b: Point64) -> Option<i128> {
+    let (ax, ay, bx, by) = (i128::from(a.x), i128::from(a.y), i128::from(b.x), i128::from(b.y));
+    (ax * by).checked_sub(ay * bx)
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// A straight line between two points, including both ends
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub struct Segment {
+    pub start: Point64,
+    pub end: Point64,
+}
+
+impl Segment {
+
+    #[must_use]
+    pub fn new(start: Point64, end: Point64) -> Self {
+        Self { start, end }
+    }
+
+    #[must_use]
+    pub fn is_axis_aligned(&self) -> bool {
+        self.start.x == self.end.x || self.start.y == self.end.y
+    }
+
+    /// The number of integer points on the segment, counting both ends
+    ///
+    /// # Panics
+    /// If the count does not fit in an `i64`
+    #[must_use]
+    pub fn lattice_points(&self) -> i64 {
+        let dx = i128::from(self.end.x) - i128::from(self.start.x);
+        let dy = i128::from(self.end.y) - i128::from(self.start.y);
+        (gcd(dx, dy) + 1).try_into().expect("Segment lattice points overflow i64")
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point64) -> bool {
+        orientation(self.start, self.end, point) == Orientation::Collinear
+            && self.in_box(point)
+    }
+
+    /// True if the segments share at least one point, including touching at an end
+    #[must_use]
+    pub fn intersects(&self, other: &Segment) -> bool {
+        let o1 = orientation(self.start, self.end, other.start);
+        let o2 = orientation(self.start, self.end, other.end);
+        let o3 = orientation(other.start, other.end, self.start);
+        let o4 = orientation(other.start, other.end, self.end);
+        if o1 != o2 && o3 != o4 && [o1, o2, o3, o4].iter().all(|&o| o != Orientation::Collinear) {
+            return true;
+        }
+        self.contains(other.start) || self.contains(other.end)
+            || other.contains(self.start) || other.contains(self.end)
+    }
+
+    /// True if the segments cross through each other at a single point
+    /// that is not an end of either segment
+    #[must_use]
+    pub fn crosses(&self, other: &Segment) -> bool {
+        let o1 = orientation(self.start, self.end, other.start);
+        let o2 = orientation(self.start, self.end, other.end);
+        let o3 = orientation(other.start, other.end, self.start);
+        let o4 = orientation(other.start, other.end, self.end);
+        [o1, o2, o3, o4].iter().all(|&o| o != Orientation::Collinear) && o1 != o2 && o3 != o4
+    }
+
+    fn in_box(&self, point: Point64) -> bool {
+        point.x >= self.start.x.min(self.end.x) && point.x <= self.start.x.max(self.end.x)
+            && point.y >= self.start.y.min(self.end.y) && point.y <= self.start.y.max(self.end.y)
+    }
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Containment {
+    Inside,
+    OnBoundary,
+    Outside,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+    /// The polygon has no area
+    Degenerate,
+}
+
+/// A simple polygon given by its corners in order. The last corner connects back to the first.
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub struct Polygon {
+    pub vertices: Vec<Point64>,
+}
+
+impl Polygon {
+
+    #[must_use]
+    pub fn new(vertices: Vec<Point64>) -> Self {
+        Self { vertices }
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = Segment> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().zip(next).map(|(&start, &end)| Segment::new(start, end))
+    }
+
+    /// Shoelace formula. Positive when the vertices go clockwise.
+    ///
+    /// # Panics
+    /// If a term or the running sum overflows an `i128`. With coordinates within ±2^61 each term is within ±2^123,
+    /// so that takes more than 16 vertices near the limit.
+    #[must_use]
+    pub fn twice_signed_area(&self) -> i128 {
+        self.edges()
+            .try_fold(0_i128, |sum, edge| sum.checked_add(cross(edge.start, edge.end)?))
+            .expect("Polygon area overflows i128")
+    }
+
+    /// Twice the area, which is always an integer for a polygon with integer vertices
+    #[must_use]
+    pub fn twice_area(&self) -> i128 {
+        self.twice_signed_area().abs()
+    }
+
+    /// The enclosed area. Only exact when the area is a whole number,
+    /// which is always the case for rectilinear polygons. Otherwise use [`Self::twice_area`].
+    ///
+    /// # Panics
+    /// If the area does not fit in an `i64`
+    #[must_use]
+    pub fn area(&self) -> i64 {
+        (self.twice_area() / 2).try_into().expect("Polygon area overflows i64")
+    }
+
+    /// Number of integer points on the edges of the polygon
+    #[must_use]
+    pub fn boundary_points(&self) -> i64 {
+        self.edges().map(|edge| edge.lattice_points() - 1).sum()
+    }
+
+    /// Number of integer points strictly inside the polygon, using Pick's theorem:
+    /// `A = I + B/2 - 1`
+    ///
+    /// # Panics
+    /// If the count does not fit in an `i64`
+    #[must_use]
+    pub fn interior_points(&self) -> i64 {
+        let interior = (self.twice_area() - i128::from(self.boundary_points()) + 2) / 2;
+        interior.try_into().expect("Polygon interior overflows i64")
+    }
+
+    /// Interior plus boundary points. For a polygon traced through the centres of grid cells,
+    /// this is the number of cells it covers.
+    #[must_use]
+    pub fn lattice_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point64) -> Containment {
+        if self.edges().any(|edge| edge.contains(point)) {
+            return Containment::OnBoundary;
+        }
+        // Cast a ray to the right and count the edges it crosses.
+        // Each edge includes its lower end but not its upper end so a vertex is only counted once.
+        let mut inside = false;
+        for edge in self.edges() {
+            let (a, b) = (edge.start, edge.end);
+            if (a.y > point.y) != (b.y > point.y) && (orientation(a, b, point) == Orientation::Clockwise) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+        if inside { Containment::Inside } else { Containment::Outside }
+    }
+
+    #[must_use]
+    pub fn winding(&self) -> Winding {
+        match self.twice_signed_area().signum() {
+            1 => Winding::Clockwise,
+            -1 => Winding::CounterClockwise,
+            _ => Winding::Degenerate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(points: &[(i64, i64)]) -> Polygon {
+        Polygon::new(points.iter().map(|&(x, y)| Point64::new(x, y)).collect())
+    }
+
+    #[test]
+    fn test_segments() {
+        let a = Segment::new(Point64::new(0, 0), Point64::new(4, 4));
+        let b = Segment::new(Point64::new(0, 4), Point64::new(4, 0));
+        let c = Segment::new(Point64::new(4, 4), Point64::new(6, 2));
+        let d = Segment::new(Point64::new(5, 5), Point64::new(9, 9));
+        assert!(a.intersects(&b) && a.crosses(&b));
+        assert!(a.intersects(&c) && !a.crosses(&c));
+        assert!(!a.intersects(&d));
+        assert_eq!(5, a.lattice_points());
+        assert!(a.contains(Point64::new(2, 2)));
+        assert!(!a.contains(Point64::new(5, 5)));
+
+        // orientation is exact all the way to the edges of i64
+        let (min, max) = (i64::MIN, i64::MAX);
+        let big = Segment::new(Point64::new(min, min), Point64::new(max, max));
+        assert!(big.crosses(&Segment::new(Point64::new(min, max), Point64::new(max, min))));
+        assert!(!big.intersects(&Segment::new(Point64::new(min, max), Point64::new(min + 1, max))));
+        assert!(big.contains(Point64::new(0, 0)) && !big.contains(Point64::new(0, 1)));
+        assert_eq!(Orientation::Clockwise, orientation(Point64::new(min, min), Point64::new(max, min), Point64::new(max, max)));
+        assert_eq!(Orientation::CounterClockwise, orientation(Point64::new(max, max), Point64::new(min, max - 1), Point64::new(min, max)));
+    }
+
+    #[test]
+    fn test_area_and_picks() {
+        // an L shape drawn clockwise on the screen
+        let shape = polygon(&[(0, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)]);
+        assert_eq!(12, shape.area());
+        assert_eq!(16, shape.boundary_points());
+        assert_eq!(5, shape.interior_points());
+        assert_eq!(21, shape.lattice_points());
+        assert_eq!(Winding::Clockwise, shape.winding());
+
+        let reversed = Polygon::new(shape.vertices.iter().rev().copied().collect());
+        assert_eq!(Winding::CounterClockwise, reversed.winding());
+        assert_eq!(12, reversed.area());
+
+        let triangle = polygon(&[(0, 0), (3, 0), (0, 1)]);
+        assert_eq!(3, triangle.twice_area());
+
+        // the documented limit, where each term of the shoelace sum is 2^123
+        let limit = 1_i64 << 61;
+        let square = polygon(&[(-limit, -limit), (limit, -limit), (limit, limit), (-limit, limit)]);
+        assert_eq!(1_i128 << 125, square.twice_signed_area());
+    }
+
+    #[test]
+    fn test_contains() {
+        let shape = polygon(&[(0, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)]);
+        assert_eq!(Containment::Inside, shape.contains(Point64::new(1, 3)));
+        assert_eq!(Containment::Inside, shape.contains(Point64::new(3, 1)));
+        assert_eq!(Containment::Outside, shape.contains(Point64::new(3, 3)));
+        assert_eq!(Containment::Outside, shape.contains(Point64::new(-1, 2)));
+        assert_eq!(Containment::OnBoundary, shape.contains(Point64::new(2, 3)));
+        assert_eq!(Containment::OnBoundary, shape.contains(Point64::new(4, 0)));
+        // the ray passes straight through the (2, 2) and (4, 2) corners
+        assert_eq!(Containment::Outside, shape.contains(Point64::new(5, 2)));
+        assert_eq!(Containment::Inside, shape.contains(Point64::new(1, 2)));
+    }
+}
@@ -0,0 +1,109 @@
+use super::point::Point;
+use super::rect::Rect;
+use super::render::Renderer;
+use super::vec2d::{Directions, Vec2d};
+
+pub mod prelude {
+    pub use crate::util::point::*;
+    pub use crate::util::vec2d::*;
+    pub use crate::util::grid::Grid;
+    pub use crate::util::sparse::SparseGrid;
+    pub use crate::util::search::GridSearch;
+    pub use crate::util::fill::{Connectivity, GridFill};
+}
+
+/// Cells addressed by [`Point`], stored densely like [`Vec2d`] or sparsely like [`SparseGrid`].
+///
+/// [`SparseGrid`]: super::sparse::SparseGrid
+pub trait Grid {
+    type Cell: Clone;
+    /// The same kind of grid, covering the same points, holding a different type of cell
+    type Mapped<U: Clone>: Grid<Cell = U>;
+
+    /// The value at `point`, or `None` if the grid has no value there
+    fn get(&self, point: Point) -> Option<&Self::Cell>;
+
+    /// Changes the value at `point`. Grids that can't hold a value at `point` panic.
+    fn set(&mut self, point: Point, value: Self::Cell);
+
+    /// The smallest rectangle that covers every cell stored in the grid
+    fn bounds(&self) -> Rect;
+
+    /// Builds a grid of the same shape with `f` applied to every cell
+    fn map<U: Clone>(&self, f: impl FnMut(&Self::Cell) -> U) -> Self::Mapped<U>;
+
+    fn in_bounds(&self, point: Point) -> bool {
+        self.bounds().contains(point)
+    }
+
+    /// The point inside the grid that `point` refers to, or `None` if it doesn't refer to one
+    fn resolve(&self, point: Point) -> Option<Point> {
+        self.in_bounds(point).then_some(point)
+    }
+
+    /// The cells next to `point` in each of the given directions that the grid has a value for
+    fn neighbors<'a>(&'a self, point: Point, directions: &'a [Directions]) -> impl Iterator<Item = (Directions, Point, &'a Self::Cell)> {
+        directions.iter().filter_map(move |&direction| {
+            let next = point + direction.to_point();
+            self.get(next).map(|cell| (direction, next, cell))
+        })
+    }
+
+    fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Directions, Point, &Self::Cell)> {
+        self.neighbors(point, &Directions::CARDINAL)
+    }
+
+    fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Directions, Point, &Self::Cell)> {
+        self.neighbors(point, &Directions::ALL)
+    }
+
+    /// Draws the cells inside [`Self::bounds`] as text, using `cell` to turn each value into a character
+    fn render<'a>(&'a self, cell: impl Fn(&Self::Cell) -> char + 'a) -> Renderer<'a> {
+        Renderer::new(self.bounds(), move |point| self.get(point).map_or(' ', &cell))
+    }
+}
+
+impl<T: Clone> Grid for Vec2d<T> {
+    type Cell = T;
+    type Mapped<U: Clone> = Vec2d<U>;
+
+    fn get(&self, point: Point) -> Option<&T> {
+        Vec2d::get(self, point)
+    }
+
+    /// Follows the edge policy, so wrapping and clamped grids can be set from outside their bounds
+    ///
+    /// # Panics
+    /// If `point` is outside a bounded grid, or outside a grid with a default value
+    fn set(&mut self, point: Point, value: T) {
+        *self.get_mut(point).unwrap_or_else(|| panic!("{point:?} is outside the grid")) = value;
+    }
+
+    fn bounds(&self) -> Rect {
+        Vec2d::bounds(self)
+    }
+
+    fn map<U: Clone>(&self, f: impl FnMut(&T) -> U) -> Vec2d<U> {
+        Vec2d::map(self, f)
+    }
+
+    fn in_bounds(&self, point: Point) -> bool {
+        Vec2d::in_bounds(self, point)
+    }
+
+    /// Follows the edge policy, so points past the edge of a wrapping grid refer to the far side.
+    /// The default value isn't stored in a cell, so points outside a grid with one are `None`.
+    fn resolve(&self, point: Point) -> Option<Point> {
+        Vec2d::resolve(self, point)
+    }
+
+    /// Follows the edge policy like [`Vec2d::step`], so the neighbors across the edge of a wrapping grid
+    /// are the cells on the far side. Only points inside the grid are neighbors, even with a default value,
+    /// so searches and fills stay on the grid.
+    fn neighbors<'a>(&'a self, point: Point, directions: &'a [Directions]) -> impl Iterator<Item = (Directions, Point, &'a T)> {
+        directions.iter().filter_map(move |&direction| {
+            let next = self.step(point, direction).filter(|&next| Vec2d::in_bounds(self, next))?;
+            Some((direction, next, &self[next]))
+        })
+    }
+}
@@ -0,0 +1,304 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use super::point::Point;
+use super::sparse::SparseGrid;
+
+/// A hexagon in axial coordinates. The third cube coordinate is `s = -q - r`.
+///
+/// `r` increases going down the screen, matching the y-down convention of [`Point`].
+/// The same coordinates work for both [`HexLayout`]s; only the directions and drawing differ.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash, Default)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// Offsets to the 6 neighbors, clockwise on screen starting from the east side of a pointy-top hex.
+/// Consecutive offsets are also a 60° clockwise rotation apart.
+const AXIAL: [Hex; 6] = [
+    Hex { q: 1, r: 0 },
+    Hex { q: 0, r: 1 },
+    Hex { q: -1, r: 1 },
+    Hex { q: -1, r: 0 },
+    Hex { q: 0, r: -1 },
+    Hex { q: 1, r: -1 },
+];
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    #[must_use]
+    pub fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    /// # Panics
+    /// If the coordinates don't add up to 0
+    #[must_use]
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Self {
+        assert_eq!(0, q + r + s, "Cube coordinates ({q},{r},{s}) must add up to 0");
+        Self { q, r }
+    }
+
+    #[must_use]
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    #[must_use]
+    pub fn cube(&self) -> (i32, i32, i32) {
+        (self.q, self.r, self.s())
+    }
+
+    /// Number of steps between the two hexes
+    #[must_use]
+    pub fn distance(&self, other: &Hex) -> i32 {
+        let diff = *self - *other;
+        (diff.q.abs() + diff.r.abs() + diff.s().abs()) / 2
+    }
+
+    /// The 6 hexes that share an edge with this one, clockwise
+    pub fn neighbors(&self) -> impl Iterator<Item = Hex> + use<> {
+        let hex = *self;
+        AXIAL.into_iter().map(move |offset| hex + offset)
+    }
+
+    /// Turns 60° clockwise around the origin
+    #[must_use]
+    pub fn rotate_right(&self) -> Hex {
+        let (_, r, s) = self.cube();
+        Hex::new(-r, -s)
+    }
+
+    /// Turns 60° counter-clockwise around the origin
+    #[must_use]
+    pub fn rotate_left(&self) -> Hex {
+        let (q, _, s) = self.cube();
+        Hex::new(-s, -q)
+    }
+
+    /// Turns around `center` by `sixth_turns` steps of 60°. Positive is clockwise.
+    #[must_use]
+    pub fn rotate_around(&self, center: &Hex, sixth_turns: i32) -> Hex {
+        let mut offset = *self - *center;
+        for _ in 0 .. sixth_turns.rem_euclid(6) {
+            offset = offset.rotate_right();
+        }
+        *center + offset
+    }
+
+    /// Every hex exactly `radius` steps away, going clockwise. A radius of 0 is just this hex.
+    pub fn ring(&self, radius: i32) -> impl Iterator<Item = Hex> + use<> {
+        let center = *self;
+        let corners = (0 .. 6).flat_map(move |side| {
+            // walk each side of the ring, starting from the corner before it
+            let corner = center + AXIAL[(side + 4) % 6] * radius;
+            (0 .. radius).map(move |step| corner + AXIAL[side] * step)
+        });
+        (radius == 0).then_some(center).into_iter().chain(corners)
+    }
+
+    /// This hex followed by every ring out to `radius`
+    pub fn spiral(&self, radius: i32) -> impl Iterator<Item = Hex> + use<> {
+        let center = *self;
+        (0 ..= radius).flat_map(move |radius| center.ring(radius))
+    }
+}
+
+/// Directions between pointy-top hexes, which have neighbors to the east and west
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum PointyDirection {
+    NorthEast,
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl PointyDirection {
+    /// Every direction, clockwise from north east
+    pub const ALL: [PointyDirection; 6] = [PointyDirection::NorthEast, PointyDirection::East, PointyDirection::SouthEast,
+        PointyDirection::SouthWest, PointyDirection::West, PointyDirection::NorthWest];
+}
+
+impl From<PointyDirection> for Hex {
+    fn from(direction: PointyDirection) -> Self {
+        match direction {
+            PointyDirection::NorthEast => Hex::new(1, -1),
+            PointyDirection::East => Hex::new(1, 0),
+            PointyDirection::SouthEast => Hex::new(0, 1),
+            PointyDirection::SouthWest => Hex::new(-1, 1),
+            PointyDirection::West => Hex::new(-1, 0),
+            PointyDirection::NorthWest => Hex::new(0, -1),
+        }
+    }
+}
+
+/// Directions between flat-top hexes, which have neighbors to the north and south
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum FlatDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl FlatDirection {
+    /// Every direction, clockwise from north
+    pub const ALL: [FlatDirection; 6] = [FlatDirection::North, FlatDirection::NorthEast, FlatDirection::SouthEast,
+        FlatDirection::South, FlatDirection::SouthWest, FlatDirection::NorthWest];
+}
+
+impl From<FlatDirection> for Hex {
+    fn from(direction: FlatDirection) -> Self {
+        match direction {
+            FlatDirection::North => Hex::new(0, -1),
+            FlatDirection::NorthEast => Hex::new(1, -1),
+            FlatDirection::SouthEast => Hex::new(1, 0),
+            FlatDirection::South => Hex::new(0, 1),
+            FlatDirection::SouthWest => Hex::new(-1, 1),
+            FlatDirection::NorthWest => Hex::new(-1, 0),
+        }
+    }
+}
+
+/// How hexes are laid out on a square grid, using doubled coordinates so that every hex
+/// lands on a whole [`Point`] and the gaps between them keep the rows or columns staggered
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum HexLayout {
+    /// Rows of hexes, two columns apart, with every other row shifted by one column
+    PointyTop,
+    /// Columns of hexes, two rows apart, with every other column shifted by one row
+    FlatTop,
+}
+
+impl HexLayout {
+
+    #[must_use]
+    pub fn to_point(self, hex: Hex) -> Point {
+        match self {
+            HexLayout::PointyTop => Point::new(2 * hex.q + hex.r, hex.r),
+            HexLayout::FlatTop => Point::new(hex.q, 2 * hex.r + hex.q),
+        }
+    }
+
+    /// The hex drawn at `point`, or `None` if `point` falls in a gap between hexes
+    #[must_use]
+    pub fn from_point(self, point: Point) -> Option<Hex> {
+        match self {
+            HexLayout::PointyTop => ((point.x - point.y) % 2 == 0).then(|| Hex::new((point.x - point.y) / 2, point.y)),
+            HexLayout::FlatTop => ((point.y - point.x) % 2 == 0).then(|| Hex::new(point.x, (point.y - point.x) / 2)),
+        }
+    }
+
+    /// Places hexes on a sparse grid so they can be rendered.
+    /// The grid's own neighbors are square neighbors in doubled coordinates, which are not the neighboring hexes,
+    /// so search and fill over [`Hex::neighbors`] instead.
+    pub fn to_sparse_grid<T: Clone>(self, hexes: impl IntoIterator<Item = (Hex, T)>, default: T) -> SparseGrid<T> {
+        let mut grid = SparseGrid::new(default);
+        for (hex, value) in hexes {
+            grid.insert(self.to_point(hex), value);
+        }
+        grid
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl SubAssign for Hex {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Self::Output {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::grid::Grid;
+
+    #[test]
+    fn test_directions_and_distance() {
+        let mut hex = Hex::ORIGIN;
+        for direction in [PointyDirection::NorthEast, PointyDirection::NorthEast, PointyDirection::East, PointyDirection::SouthEast] {
+            hex += direction.into();
+        }
+        assert_eq!(Hex::from_cube(3, -1, -2), hex);
+        assert_eq!(3, hex.distance(&Hex::ORIGIN));
+        assert!(hex.neighbors().all(|neighbor| neighbor.distance(&hex) == 1));
+        // opposite directions cancel out in both layouts
+        assert_eq!(Hex::ORIGIN, Hex::from(FlatDirection::North) + FlatDirection::South.into());
+        assert_eq!(Hex::ORIGIN, Hex::from(PointyDirection::SouthWest) + PointyDirection::NorthEast.into());
+    }
+
+    #[test]
+    fn test_rotation() {
+        let east = Hex::from(PointyDirection::East);
+        assert_eq!(Hex::from(PointyDirection::SouthEast), east.rotate_right());
+        assert_eq!(Hex::from(PointyDirection::NorthEast), east.rotate_left());
+        assert_eq!(-east, east.rotate_around(&Hex::ORIGIN, 3));
+        let center = Hex::new(2, -1);
+        assert_eq!(center + east.rotate_left(), (center + east).rotate_around(&center, -1));
+    }
+
+    #[test]
+    fn test_rings_and_spirals() {
+        let center = Hex::new(1, 1);
+        assert_eq!(vec![center], center.ring(0).collect::<Vec<_>>());
+        let ring = center.ring(2).collect::<Vec<_>>();
+        assert_eq!(12, ring.len());
+        assert!(ring.iter().all(|hex| hex.distance(&center) == 2));
+        assert!(ring.windows(2).all(|pair| pair[0].distance(&pair[1]) == 1));
+        assert_eq!(19, center.spiral(2).count());
+    }
+
+    #[test]
+    fn test_render() {
+        let labels = Hex::ORIGIN.spiral(1).zip("oabcdef".chars()).collect::<Vec<_>>();
+        let pointy = HexLayout::PointyTop.to_sparse_grid(labels.clone(), ' ');
+        assert_eq!(" a b \nf o c\n e d ", pointy.render(|&c| c).to_string());
+        let flat = HexLayout::FlatTop.to_sparse_grid(labels, ' ');
+        assert_eq!(" a \nf b\n o \ne c\n d ", flat.render(|&c| c).to_string());
+        for point in pointy.points() {
+            assert_eq!(Some(point), HexLayout::PointyTop.from_point(point).map(|hex| HexLayout::PointyTop.to_point(hex)));
+        }
+        assert_eq!(None, HexLayout::PointyTop.from_point(Point::new(1, 0)));
+    }
+}
@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io;
+use std::path::Path;
+use super::vec2d::Vec2d;
+
+/// A color as red, green and blue
+pub type Rgb = [u8; 3];
+
+/// An RGB image, for saving grids to look at in an image viewer
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    // rows top to bottom
+    pixels: Vec<Rgb>,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum GifError {
+    NoFrames,
+    /// Every frame must be the same size as the first
+    SizeMismatch { frame: usize },
+    /// GIFs can only use 256 colors
+    TooManyColors(usize),
+    /// GIFs store the width and height in 16 bits
+    TooLarge { width: usize, height: usize },
+}
+
+impl Display for GifError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GifError::NoFrames => write!(f, "An animation needs at least one frame"),
+            GifError::SizeMismatch { frame } => write!(f, "Frame {frame} is a different size than the first frame"),
+            GifError::TooManyColors(colors) => write!(f, "{colors} colors used, but a GIF can only have 256"),
+            GifError::TooLarge { width, height } => write!(f, "{width}x{height} is too large, a GIF can be at most 65535x65535"),
+        }
+    }
+}
+
+impl Error for GifError {}
+
+impl Image {
+
+    /// Draws every cell as a `scale` x `scale` square, colored by `palette`
+    ///
+    /// # Panics
+    /// If `scale` is 0
+    pub fn from_grid<T: Clone>(grid: &Vec2d<T>, scale: usize, palette: impl Fn(&T) -> Rgb) -> Self {
+        assert!(scale > 0, "Scale must be at least 1");
+        let (width, height) = (grid.width() as usize * scale, grid.height() as usize * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in grid.rows() {
+            let colors = row.iter().map(&palette).collect::<Vec<_>>();
+            for _ in 0 .. scale {
+                pixels.extend(colors.iter().flat_map(|&color| std::iter::repeat_n(color, scale)));
+            }
+        }
+        Self { width, height, pixels }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Binary PPM (P6), the simplest format most image viewers can open
+    #[must_use]
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flatten());
+        bytes
+    }
+
+    /// PNG with the pixel data stored without compression
+    #[must_use]
+    pub fn to_png(&self) -> Vec<u8> {
+        // every row starts with a filter type byte, 0 for no filtering
+        let mut raw = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width) {
+            raw.push(0);
+            raw.extend(row.iter().flatten());
+        }
+
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, deflate, adaptive filtering, no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        write_png_chunk(&mut png, b"IHDR", &header);
+        write_png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    pub fn save_ppm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_ppm())
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_png())
+    }
+}
+
+impl<T: Clone> Vec2d<T> {
+
+    /// See [`Image::from_grid`]
+    pub fn to_image(&self, scale: usize, palette: impl Fn(&T) -> Rgb) -> Image {
+        Image::from_grid(self, scale, palette)
+    }
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start ..]);
+    png.extend(crc.to_be_bytes());
+}
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(u32::MAX, |crc, &byte| CRC_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8))
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1_u32, 0_u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    b << 16 | a
+}
+
+/// A zlib stream made of deflate blocks that store the data as is
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // deflate with a 32K window and no preset dictionary, with check bits that make the header a multiple of 31
+    let mut stream = vec![0x78, 0x01];
+    let blocks = data.chunks(u16::MAX as usize).collect::<Vec<_>>();
+    if blocks.is_empty() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    for (i, block) in blocks.iter().enumerate() {
+        let last = i == blocks.len() - 1;
+        stream.push(u8::from(last));
+        let len = block.len() as u16;
+        stream.extend(len.to_le_bytes());
+        stream.extend((!len).to_le_bytes());
+        stream.extend(*block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+/// Encodes the frames as a looping animated GIF, showing each frame for `delay` hundredths of a second
+pub fn to_gif(frames: &[Image], delay: u16) -> Result<Vec<u8>, GifError> {
+    let first = frames.first().ok_or(GifError::NoFrames)?;
+    if let Some(frame) = frames.iter().position(|frame| frame.width != first.width || frame.height != first.height) {
+        return Err(GifError::SizeMismatch { frame });
+    }
+    let (Ok(width), Ok(height)) = (u16::try_from(first.width), u16::try_from(first.height)) else {
+        return Err(GifError::TooLarge { width: first.width, height: first.height });
+    };
+
+    // one palette shared by every frame
+    let mut palette = HashMap::new();
+    let mut colors = vec![];
+    for &color in frames.iter().flat_map(|frame| &frame.pixels) {
+        palette.entry(color).or_insert_with(|| {
+            colors.push(color);
+            colors.len() - 1
+        });
+    }
+    if colors.len() > 256 {
+        return Err(GifError::TooManyColors(colors.len()));
+    }
+    // the color table size is a power of 2 of at least 2 entries
+    let bits = colors.len().next_power_of_two().max(2).trailing_zeros() as u8;
+    colors.resize(1 << bits, [0, 0, 0]);
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend(width.to_le_bytes());
+    gif.extend(height.to_le_bytes());
+    gif.extend([0x80 | 0x70 | (bits - 1), 0, 0]);
+    gif.extend(colors.iter().flatten());
+    // loop forever
+    gif.extend([0x21, 0xff, 0x0b]);
+    gif.extend(b"NETSCAPE2.0");
+    gif.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    let min_code_size = bits.max(2);
+    for frame in frames {
+        gif.extend([0x21, 0xf9, 0x04, 0x00]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0x00, 0x00]);
+
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend(width.to_le_bytes());
+        gif.extend(height.to_le_bytes());
+        gif.push(0);
+
+        let indexes = frame.pixels.iter().map(|color| palette[color] as u8).collect::<Vec<_>>();
+        gif.push(min_code_size);
+        for block in lzw_encode(&indexes, min_code_size).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+    gif.push(0x3b);
+    Ok(gif)
+}
+
+/// Writes codes of varying width, least significant bit first
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += width;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// The variable code width LZW that GIF uses, restarting the dictionary when it fills up
+fn lzw_encode(indexes: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1_u16 << min_code_size;
+    let end = clear + 1;
+    let mut writer = BitWriter { bytes: vec![], buffer: 0, bits: 0 };
+    let mut dictionary = HashMap::new();
+    let mut next_code = end + 1;
+    let mut code_size = min_code_size + 1;
+    writer.write(clear, code_size);
+
+    let mut prefix = None;
+    for &index in indexes {
+        let Some(current) = prefix else {
+            prefix = Some(u16::from(index));
+            continue;
+        };
+        if let Some(&code) = dictionary.get(&(current, index)) {
+            prefix = Some(code);
+            continue;
+        }
+        writer.write(current, code_size);
+        if next_code < 4096 {
+            dictionary.insert((current, index), next_code);
+            next_code += 1;
+            // the decoder adds its entries one code behind, so widen once it will need the new code
+            if next_code > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            writer.write(clear, code_size);
+            dictionary.clear();
+            next_code = end + 1;
+            code_size = min_code_size + 1;
+        }
+        prefix = Some(u16::from(index));
+    }
+    if let Some(current) = prefix {
+        writer.write(current, code_size);
+        // the decoder adds its entry for the last code before reading the end code, which can widen it
+        if next_code == 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+    }
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads LZW codes the way a GIF decoder would
+    fn lzw_decode(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1_usize << min_code_size;
+        let mut bits = bytes.iter().flat_map(|&byte| (0 .. 8).map(move |bit| (byte >> bit) & 1));
+        let mut read = |width: u8| (0 .. width).map(|i| usize::from(bits.next().unwrap()) << i).sum::<usize>();
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = vec![];
+        loop {
+            let code = read(code_size);
+            if code == clear {
+                table = (0 .. clear).map(|i| vec![i as u8]).chain([vec![], vec![]]).collect();
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return output;
+            }
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.clone(), vec![previous[0]]].concat(),
+                (None, None) => panic!("Invalid code {code}"),
+            };
+            output.extend(&entry);
+            if let Some(previous) = previous {
+                if table.len() < 4096 {
+                    table.push([previous, vec![entry[0]]].concat());
+                }
+                if table.len() >= 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            previous = Some(entry);
+        }
+    }
+
+    fn checkerboard() -> Vec2d<bool> {
+        Vec2d::from_rows((0 .. 3).map(|y| (0 .. 4).map(move |x| (x + y) % 2 == 0))).unwrap()
+    }
+
+    #[test]
+    fn test_scale_and_ppm() {
+        let image = checkerboard().to_image(2, |&on| if on { [255, 255, 255] } else { [0, 0, 128] });
+        assert_eq!((8, 6), (image.width(), image.height()));
+        assert_eq!([255, 255, 255], image.pixel(1, 1));
+        assert_eq!([0, 0, 128], image.pixel(2, 1));
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n8 6\n255\n"));
+        assert_eq!(11 + 8 * 6 * 3, ppm.len());
+    }
+
+    #[test]
+    fn test_png() {
+        assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+        assert_eq!(0x11e6_0398, adler32(b"Wikipedia"));
+        let image = checkerboard().to_image(1, |&on| if on { [255, 0, 0] } else { [0, 255, 0] });
+        let png = image.to_png();
+        assert_eq!(b"\x89PNG\r\n\x1a\n", &png[.. 8]);
+        assert_eq!(b"IHDR", &png[12 .. 16]);
+        assert_eq!(b"IEND", &png[png.len() - 8 .. png.len() - 4]);
+        // 3 rows of a filter byte and 4 pixels, stored in one block after the zlib and block headers
+        let idat = &png[33 + 8 ..];
+        assert_eq!([0x78, 0x01, 0x01, 39, 0, !39, 0xff, 0], idat[.. 8]);
+        assert_eq!([0, 255, 0, 0, 0, 255, 0], idat[7 .. 14]);
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let repetitive = (0 .. 20_000).map(|i| (i / 7 % 4) as u8).collect::<Vec<_>>();
+        let noisy = (0 .. 20_000_u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8).collect::<Vec<_>>();
+        for (data, min_code_size) in [(repetitive, 2), (noisy, 8), (vec![1], 2), (vec![], 2)] {
+            assert_eq!(data, lzw_decode(&lzw_encode(&data, min_code_size), min_code_size));
+        }
+
+        // short inputs end at every point of the code widths, including right as the table fills up
+        assert_eq!(vec![2, 0, 3, 1, 0, 2, 1, 3, 2, 0, 3, 1, 0], lzw_decode(&lzw_encode(&[2, 0, 3, 1, 0, 2, 1, 3, 2, 0, 3, 1, 0], 2), 2));
+        let mut seed = 12_345_u64;
+        for _ in 0 .. 10_000 {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            let len = (seed >> 33) as usize % 40;
+            let data = (0 .. len).map(|i| ((seed >> (i % 31)) % 4) as u8).collect::<Vec<_>>();
+            assert_eq!(data, lzw_decode(&lzw_encode(&data, 2), 2));
+        }
+    }
+
+    #[test]
+    fn test_gif() {
+        let frames = (0 .. 3)
+            .map(|shift| Vec2d::from_rows((0 .. 5).map(|y| (0 .. 5).map(move |x| (x + y + shift) % 3))).unwrap())
+            .map(|grid| grid.to_image(2, |&v| [v as u8 * 100, 0, 0]))
+            .collect::<Vec<_>>();
+        let gif = to_gif(&frames, 10).unwrap();
+        assert!(gif.starts_with(b"GIF89a\x0a\x00\x0a\x00"));
+        assert_eq!(Some(&0x3b), gif.last());
+        // 3 colors round up to a 4 color table
+        assert_eq!(0xf1, gif[10]);
+        assert_eq!(3, gif.windows(3).filter(|w| *w == [0x21, 0xf9, 0x04]).count());
+
+        assert_eq!(Err(GifError::NoFrames), to_gif(&[], 10));
+        let small = Vec2d::filled(1, 1, 0).to_image(1, |_| [0, 0, 0]);
+        assert_eq!(Err(GifError::SizeMismatch { frame: 1 }), to_gif(&[frames[0].clone(), small], 10));
+        let wide = Vec2d::filled(65_536, 1, 0).to_image(1, |_| [0, 0, 0]);
+        assert_eq!(Err(GifError::TooLarge { width: 65_536, height: 1 }), to_gif(&[wide], 10));
+    }
+}
@@ -1,9 +1,21 @@
 pub mod vec2d;
+pub mod vec3d;
 pub mod point;
-
-pub mod grid {
-    pub mod prelude {
-        pub use crate::util::point::*;
-        pub use crate::util::vec2d::*;
-    }
-}
\ No newline at end of file
+pub mod point3d;
+pub mod pointn;
+pub mod num;
+pub mod geometry;
+pub mod rect;
+pub mod compress;
+pub mod render;
+pub mod transform;
+pub mod search;
+pub mod fill;
+pub mod grid;
+pub mod sparse;
+pub mod automaton;
+pub mod bitgrid;
+pub mod hex;
+pub mod image;
+pub mod pattern;
+pub mod cycle;
\ No newline at end of file
@@ -0,0 +1,276 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::iter::{Product, Sum};
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The primitive integer types, with the checked and saturating operations they all share.
+pub trait Integer:
+    Copy
+    + Eq
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + Default
+    + FromStr<Err = ParseIntError>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + AddAssign
+    + SubAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    #[must_use]
+    fn saturating_add(self, rhs: Self) -> Self;
+    #[must_use]
+    fn saturating_sub(self, rhs: Self) -> Self;
+    #[must_use]
+    fn saturating_mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> { <$t>::checked_add(self, rhs) }
+                fn checked_sub(self, rhs: Self) -> Option<Self> { <$t>::checked_sub(self, rhs) }
+                fn checked_mul(self, rhs: Self) -> Option<Self> { <$t>::checked_mul(self, rhs) }
+                fn saturating_add(self, rhs: Self) -> Self { <$t>::saturating_add(self, rhs) }
+                fn saturating_sub(self, rhs: Self) -> Self { <$t>::saturating_sub(self, rhs) }
+                fn saturating_mul(self, rhs: Self) -> Self { <$t>::saturating_mul(self, rhs) }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+static OVERFLOW_CHECKS: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static SCOPED_OVERFLOW_CHECKS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Makes every [`Checked`] operation verify that it did not overflow.
+/// An overflow unwinds with an [`Overflow`] payload that [`catch_overflow`] turns into an error.
+pub fn enable_overflow_checks() {
+    if OVERFLOW_CHECKS.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    // The runner reports overflows itself, so keep the default hook from printing them as panics
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if info.payload().downcast_ref::<Overflow>().is_none() {
+            default_hook(info);
+        }
+    }));
+}
+
+/// Whether [`Checked`] operations on this thread verify that they did not overflow
+#[must_use]
+pub fn overflow_checks_enabled() -> bool {
+    OVERFLOW_CHECKS.load(Ordering::Relaxed) || SCOPED_OVERFLOW_CHECKS.get()
+}
+
+/// Runs `f` with overflow checks turned on for [`Checked`] operations on the current thread only.
+/// Unlike [`enable_overflow_checks`], the panic hook is left alone and checks go back to how they were afterwards,
+/// even if `f` panics.
+pub fn with_overflow_checks<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_OVERFLOW_CHECKS.set(self.0);
+        }
+    }
+
+    let _restore = Restore(SCOPED_OVERFLOW_CHECKS.replace(true));
+    f()
+}
+
+/// Runs `f`, returning the first overflow raised by a [`Checked`] operation as an error.
+/// Any other panic is passed along untouched.
+pub fn catch_overflow<R>(f: impl FnOnce() -> R) -> Result<R, Overflow> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload: Box<dyn Any + Send>| {
+        match payload.downcast::<Overflow>() {
+            Ok(overflow) => *overflow,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    })
+}
+
+/// The operation that overflowed, for example `9223372036854775807 + 1` on an `i64`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub operation: String,
+    pub type_name: &'static str,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "overflow in `{}` ({})", self.operation, self.type_name)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// An integer for accumulating answers. Behaves like `T` unless [`enable_overflow_checks`]
+/// has been called or it is used inside [`with_overflow_checks`], in which case an overflowing operation reports itself instead of wrapping.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Debug, Hash, Default)]
+pub struct Checked<T>(pub T);
+
+impl<T: Integer> Checked<T> {
+    fn apply(self, rhs: T, symbol: &str, checked: fn(T, T) -> Option<T>, unchecked: fn(T, T) -> T) -> Self {
+        if !overflow_checks_enabled() {
+            return Checked(unchecked(self.0, rhs));
+        }
+        match checked(self.0, rhs) {
+            Some(result) => Checked(result),
+            None => panic::panic_any(Overflow {
+                operation: format!("{} {symbol} {rhs}", self.0),
+                type_name: std::any::type_name::<T>(),
+            }),
+        }
+    }
+}
+
+impl<T: Display> Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Integer> From<T> for Checked<T> {
+    fn from(value: T) -> Self {
+        Checked(value)
+    }
+}
+
+impl<T: Integer> Add<T> for Checked<T> {
+    type Output = Checked<T>;
+    fn add(self, rhs: T) -> Self::Output {
+        self.apply(rhs, "+", T::checked_add, |a, b| a + b)
+    }
+}
+
+impl<T: Integer> Add<Checked<T>> for Checked<T> {
+    type Output = Checked<T>;
+    fn add(self, rhs: Checked<T>) -> Self::Output {
+        self + rhs.0
+    }
+}
+
+impl<T: Integer> Sub<T> for Checked<T> {
+    type Output = Checked<T>;
+    fn sub(self, rhs: T) -> Self::Output {
+        self.apply(rhs, "-", T::checked_sub, |a, b| a - b)
+    }
+}
+
+impl<T: Integer> Sub<Checked<T>> for Checked<T> {
+    type Output = Checked<T>;
+    fn sub(self, rhs: Checked<T>) -> Self::Output {
+        self - rhs.0
+    }
+}
+
+impl<T: Integer> Mul<T> for Checked<T> {
+    type Output = Checked<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        self.apply(rhs, "*", T::checked_mul, |a, b| a * b)
+    }
+}
+
+impl<T: Integer> Mul<Checked<T>> for Checked<T> {
+    type Output = Checked<T>;
+    fn mul(self, rhs: Checked<T>) -> Self::Output {
+        self * rhs.0
+    }
+}
+
+impl<T: Integer> AddAssign<T> for Checked<T> {
+    fn add_assign(&mut self, rhs: T) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Integer> SubAssign<T> for Checked<T> {
+    fn sub_assign(&mut self, rhs: T) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Integer> MulAssign<T> for Checked<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Integer> Sum<T> for Checked<T> {
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Checked(T::ZERO), |sum, n| sum + n)
+    }
+}
+
+impl<T: Integer> Sum<Checked<T>> for Checked<T> {
+    fn sum<I: Iterator<Item = Checked<T>>>(iter: I) -> Self {
+        iter.fold(Checked(T::ZERO), |sum, n| sum + n)
+    }
+}
+
+impl<T: Integer> Product<T> for Checked<T> {
+    fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Checked(T::ONE), |product, n| product * n)
+    }
+}
+
+impl<T: Integer> Product<Checked<T>> for Checked<T> {
+    fn product<I: Iterator<Item = Checked<T>>>(iter: I) -> Self {
+        iter.fold(Checked(T::ONE), |product, n| product * n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_accumulator() {
+        // scoped to this thread, so other tests still see the default unchecked behaviour
+        with_overflow_checks(|| {
+            let sum = catch_overflow(|| [1_i64, 2, 3].into_iter().sum::<Checked<i64>>());
+            assert_eq!(Ok(Checked(6)), sum);
+
+            let overflow = catch_overflow(|| [i64::MAX - 1, 1, 1].into_iter().sum::<Checked<i64>>()).unwrap_err();
+            assert_eq!(format!("{} + 1", i64::MAX), overflow.operation);
+            assert_eq!("i64", overflow.type_name);
+
+            let overflow = catch_overflow(|| Checked(200_u8) * 2).unwrap_err();
+            assert_eq!("overflow in `200 * 2` (u8)", overflow.to_string());
+        });
+    }
+
+    #[test]
+    fn test_scoped_checks_are_restored() {
+        assert!(!overflow_checks_enabled());
+        let result = std::panic::catch_unwind(|| with_overflow_checks(|| {
+            assert!(overflow_checks_enabled());
+            panic!("leaving the scope early");
+        }));
+        assert!(result.is_err());
+        assert!(!overflow_checks_enabled());
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash, RandomState};
+use super::point::Point;
+use super::vec2d::Vec2d;
+
+/// One of the 8 ways a grid can be turned or mirrored
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Symmetry {
+    Identity,
+    RotateCw,
+    Rotate180,
+    RotateCcw,
+    FlipH,
+    FlipV,
+    /// Mirrored across the top-left to bottom-right diagonal
+    Transpose,
+    /// Mirrored across the top-right to bottom-left diagonal
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [Symmetry::Identity, Symmetry::RotateCw, Symmetry::Rotate180, Symmetry::RotateCcw,
+        Symmetry::FlipH, Symmetry::FlipV, Symmetry::Transpose, Symmetry::AntiTranspose];
+
+    #[must_use]
+    pub fn apply<T: Clone>(self, grid: &Vec2d<T>) -> Vec2d<T> {
+        match self {
+            Symmetry::Identity => grid.clone(),
+            Symmetry::RotateCw => grid.rotate_cw(),
+            Symmetry::Rotate180 => grid.rotate_180(),
+            Symmetry::RotateCcw => grid.rotate_ccw(),
+            Symmetry::FlipH => grid.flip_h(),
+            Symmetry::FlipV => grid.flip_v(),
+            Symmetry::Transpose => grid.transpose(),
+            Symmetry::AntiTranspose => grid.rotate_180().transpose(),
+        }
+    }
+}
+
+/// Where a pattern was found by [`Vec2d::find_pattern`]
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub struct PatternMatch {
+    /// The grid point under the top-left cell of the pattern, after the symmetry was applied to it
+    pub point: Point,
+    pub symmetry: Symmetry,
+}
+
+/// The hash of every `width` x `height` window of a grid of cell hashes, in reading order of the window's top-left cell.
+/// Uses a polynomial rolling hash along each row, then another down each column of row hashes.
+fn window_hashes(cells: &[u64], line_len: usize, width: usize, height: usize) -> (Vec<u64>, usize) {
+    const ROW_BASE: u64 = 0x9e37_79b9_7f4a_7c15;
+    const COL_BASE: u64 = 0xc2b2_ae3d_27d4_eb4f;
+    let rows = cells.len() / line_len;
+    let windows_per_row = line_len + 1 - width;
+    let row_drop = ROW_BASE.wrapping_pow(width as u32 - 1);
+    let col_drop = COL_BASE.wrapping_pow(height as u32 - 1);
+
+    let mut row_hashes = Vec::with_capacity(rows * windows_per_row);
+    for row in cells.chunks(line_len) {
+        let mut hash = row[.. width].iter().fold(0_u64, |hash, &cell| hash.wrapping_mul(ROW_BASE).wrapping_add(cell));
+        row_hashes.push(hash);
+        for x in width .. line_len {
+            hash = hash.wrapping_sub(row[x - width].wrapping_mul(row_drop)).wrapping_mul(ROW_BASE).wrapping_add(row[x]);
+            row_hashes.push(hash);
+        }
+    }
+
+    let mut hashes = vec![0; (rows + 1 - height) * windows_per_row];
+    for x in 0 .. windows_per_row {
+        let column = |y: usize| row_hashes[y * windows_per_row + x];
+        let mut hash = (0 .. height).fold(0_u64, |hash, y| hash.wrapping_mul(COL_BASE).wrapping_add(column(y)));
+        hashes[x] = hash;
+        for y in height .. rows {
+            hash = hash.wrapping_sub(column(y - height).wrapping_mul(col_drop)).wrapping_mul(COL_BASE).wrapping_add(column(y));
+            hashes[(y + 1 - height) * windows_per_row + x] = hash;
+        }
+    }
+    (hashes, windows_per_row)
+}
+
+/// A run of consecutive non-wildcard cells in one row of a pattern
+struct Run {
+    offset: Point,
+    len: usize,
+    hash: u64,
+}
+
+/// Splits each row of a pattern into its runs of non-wildcard cells, hashing each run like a 1-row window
+fn runs(cells: &[Option<u64>], line_len: usize) -> Vec<Run> {
+    let mut runs = vec![];
+    for (y, row) in cells.chunks(line_len).enumerate() {
+        let mut x = 0;
+        while x < line_len {
+            let len = row[x ..].iter().take_while(|cell| cell.is_some()).count();
+            if len > 0 {
+                let run_cells = row[x .. x + len].iter().map(|cell| cell.unwrap()).collect::<Vec<_>>();
+                let (hash, _) = window_hashes(&run_cells, len, len, 1);
+                runs.push(Run { offset: Point::new(x as i32, y as i32), len, hash: hash[0] });
+            }
+            x += len + 1;
+        }
+    }
+    runs
+}
+
+impl<T: Clone + Eq + Hash> Vec2d<T> {
+
+    /// Finds every place `pattern` appears in the grid, trying it in each of the given symmetries.
+    /// `None` cells in the pattern are wildcards that match anything.
+    ///
+    /// Patterns without wildcards are found with a rolling hash in time proportional to the grid's size.
+    /// Patterns with wildcards are split into runs of non-wildcard cells along each row, and each run is
+    /// found with a rolling hash, so the time is proportional to the grid's size times the number of runs.
+    /// Symmetries that turn the pattern into one already searched for are skipped,
+    /// so a symmetric pattern is only reported once at each point.
+    /// Matches are sorted in reading order of their point.
+    pub fn find_pattern(&self, pattern: &Vec2d<Option<T>>, symmetries: &[Symmetry]) -> Vec<PatternMatch> {
+        let hasher = RandomState::new();
+        let cells = self.grid.iter().map(|cell| hasher.hash_one(cell)).collect::<Vec<_>>();
+        // the grid's window hashes by window size, shared between symmetries and runs of the same size
+        let mut hashes_by_size: HashMap<(usize, usize), (Vec<u64>, usize)> = HashMap::new();
+        let line_len = self.line_len as usize;
+        let mut searched: Vec<Vec2d<Option<T>>> = vec![];
+        let mut matches = vec![];
+        for &symmetry in symmetries {
+            let oriented = symmetry.apply(pattern);
+            if searched.contains(&oriented) || oriented.width() > self.width() || oriented.height() > self.height() {
+                continue;
+            }
+            // equal hashes are almost always a match, but are checked in case of a collision
+            let is_match = |point: Point| oriented.enumerate()
+                .all(|(offset, cell)| cell.as_ref().is_none_or(|cell| *cell == self[point + offset]));
+            let (width, height) = (oriented.width() as usize, oriented.height() as usize);
+            let pattern_cells = oriented.grid.iter().map(|cell| cell.as_ref().map(|cell| hasher.hash_one(cell))).collect::<Vec<_>>();
+
+            if pattern_cells.iter().all(Option::is_some) {
+                let pattern_cells = pattern_cells.into_iter().flatten().collect::<Vec<_>>();
+                let (target, _) = window_hashes(&pattern_cells, width, width, height);
+                let (hashes, windows_per_row) = hashes_by_size.entry((width, height))
+                    .or_insert_with(|| window_hashes(&cells, line_len, width, height));
+                let windows_per_row = *windows_per_row;
+                matches.extend(hashes.iter().enumerate()
+                    .filter(|&(_, &hash)| hash == target[0])
+                    .map(|(idx, _)| Point::new((idx % windows_per_row) as i32, (idx / windows_per_row) as i32))
+                    .filter(|&point| is_match(point))
+                    .map(|point| PatternMatch { point, symmetry }));
+            } else {
+                let runs = runs(&pattern_cells, width);
+                for run in &runs {
+                    hashes_by_size.entry((run.len, 1)).or_insert_with(|| window_hashes(&cells, line_len, run.len, 1));
+                }
+                let run_matches = |point: Point| runs.iter().all(|run| {
+                    let (hashes, windows_per_row) = &hashes_by_size[&(run.len, 1)];
+                    let start = point + run.offset;
+                    hashes[start.y as usize * windows_per_row + start.x as usize] == run.hash
+                });
+                let last = Point::new(self.width() - oriented.width(), self.height() - oriented.height());
+                matches.extend((0 ..= last.y)
+                    .flat_map(|y| (0 ..= last.x).map(move |x| Point::new(x, y)))
+                    .filter(|&point| run_matches(point) && is_match(point))
+                    .map(|point| PatternMatch { point, symmetry }));
+            }
+            searched.push(oriented);
+        }
+        // stable, so matches at the same point stay in the order of `symmetries`
+        matches.sort_by_key(|m| m.point);
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Vec2d<char> {
+        Vec2d::parse(text, |c| c).unwrap()
+    }
+
+    fn pattern(text: &str) -> Vec2d<Option<char>> {
+        Vec2d::parse(text, |c| (c != '?').then_some(c)).unwrap()
+    }
+
+    #[test]
+    fn test_exact_and_wildcard() {
+        let haystack = grid("abab\nbaba\nabab");
+        let points = |found: Vec<PatternMatch>| found.into_iter().map(|m| m.point).collect::<Vec<_>>();
+        assert_eq!(vec![Point::new(0, 0), Point::new(2, 0), Point::new(1, 1)],
+            points(haystack.find_pattern(&pattern("ab\nba"), &[Symmetry::Identity])));
+        assert_eq!(vec![Point::new(0, 0), Point::new(2, 0), Point::new(1, 1)],
+            points(haystack.find_pattern(&pattern("a?\n?a"), &[Symmetry::Identity])));
+        assert!(haystack.find_pattern(&pattern("aa"), &[Symmetry::Identity]).is_empty());
+        assert!(haystack.find_pattern(&pattern("ababa"), &[Symmetry::Identity]).is_empty());
+    }
+
+    #[test]
+    fn test_symmetries() {
+        // an L shape of X, turned to face up and right
+        let haystack = grid(".....\n.XXX.\n.X...\n.....");
+        let shape = pattern("X.\nX.\nXX");
+        let found = haystack.find_pattern(&shape, &Symmetry::ALL);
+        assert_eq!(vec![PatternMatch { point: Point::new(1, 1), symmetry: Symmetry::RotateCw }], found);
+
+        // a symmetric pattern is only found once at each point
+        let square = grid("XX\nXX");
+        assert_eq!(1, square.find_pattern(&pattern("XX\nXX"), &Symmetry::ALL).len());
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let haystack = Vec2d::from_rows((0 .. 60).map(|y| (0 .. 70).map(move |x: u32| (x * 7 + y * 3).is_multiple_of(5) || (x ^ y).is_multiple_of(11)))).unwrap();
+        let needle = Vec2d::from_rows((0 .. 2).map(|y| (0 .. 3).map(move |x| Some(x % 2 == y)))).unwrap();
+        let expected = (0 .. 59).flat_map(|y| (0 .. 68).map(move |x| Point::new(x, y)))
+            .filter(|&point| needle.enumerate().all(|(offset, cell)| Some(haystack[point + offset]) == *cell))
+            .collect::<Vec<_>>();
+        let found = haystack.find_pattern(&needle, &[Symmetry::Identity]).into_iter().map(|m| m.point).collect::<Vec<_>>();
+        assert!(!expected.is_empty());
+        assert_eq!(expected, found);
+    }
+
+    #[test]
+    fn test_wildcards_match_brute_force() {
+        let haystack = Vec2d::from_rows((0 .. 200).map(|y| (0 .. 300).map(move |x: u32| (x * 7 + y * 3) % 5 < 2 || (x ^ y).is_multiple_of(11)))).unwrap();
+        let needle = Vec2d::from_rows([
+            vec![Some(true), None, Some(false)],
+            vec![None, Some(true), Some(true)],
+            vec![Some(false), None, None],
+        ]).unwrap();
+        for symmetry in Symmetry::ALL {
+            let oriented = symmetry.apply(&needle);
+            let last = Point::new(haystack.width() - oriented.width(), haystack.height() - oriented.height());
+            let expected = (0 ..= last.y).flat_map(|y| (0 ..= last.x).map(move |x| Point::new(x, y)))
+                .filter(|&point| oriented.enumerate().all(|(offset, cell)| cell.is_none_or(|cell| haystack[point + offset] == cell)))
+                .collect::<Vec<_>>();
+            let found = haystack.find_pattern(&needle, &[symmetry]).into_iter().map(|m| m.point).collect::<Vec<_>>();
+            assert!(!expected.is_empty());
+            assert_eq!(expected, found);
+        }
+    }
+}
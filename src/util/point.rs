@@ -1,67 +1,169 @@
-use std::ops::{Add, Mul, Sub};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use super::num::Integer;
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
+/// Signed integer types that can be used as the components of a [`Point`].
+pub trait Coordinate: Integer + Neg<Output = Self> {
+    #[must_use]
+    fn abs(self) -> Self;
+    fn checked_neg(self) -> Option<Self>;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn checked_neg(self) -> Option<Self> {
+                    <$t>::checked_neg(self)
+                }
+            }
+        )*
+    };
 }
 
-impl Point {
+impl_coordinate!(i8, i16, i32, i64, i128, isize);
+
+/// A point on a 2d grid. Defaults to `i32` components,
+/// which is what [`crate::util::vec2d::Vec2d`] uses for indexing.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash, Default)]
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point for puzzles where coordinates are too large for an `i32`
+pub type Point64 = Point<i64>;
+
+impl<T: Coordinate> Point<T> {
 
     #[must_use]
-    pub fn new(x: i32, y: i32) -> Self {
+    pub fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
 
     #[must_use]
-    pub fn manhattan_distance(&self, other: &Point) -> i32 {
-        i32::abs(other.x - self.x) + i32::abs(other.y - self.y)
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        (other.x - self.x).abs() + (other.y - self.y).abs()
     }
-}
 
-impl Add<Point> for Point {
-    type Output = Point;
-    fn add(self, rhs: Point) -> Point {
-        let x = self.x + rhs.x;
-        let y = self.y + rhs.y;
-        Self { x, y }
+    /// Adds two points, returning `None` if either component overflows
+    #[must_use]
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(Self::new(self.x.checked_add(rhs.x)?, self.y.checked_add(rhs.y)?))
     }
-}
 
-impl Sub<Point> for Point {
-    type Output = Point;
-    fn sub(self, rhs: Point) -> Point {
-        let x = self.x - rhs.x;
-        let y = self.y - rhs.y;
-        Self { x, y }
+    /// Subtracts two points, returning `None` if either component overflows
+    #[must_use]
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(Self::new(self.x.checked_sub(rhs.x)?, self.y.checked_sub(rhs.y)?))
     }
-}
 
-impl Mul<i32> for Point {
-    type Output = Point;
-    fn mul(self, rhs: i32) -> Self::Output {
-        let x = self.x * rhs;
-        let y = self.y * rhs;
-        Self { x, y }
+    /// Scales the point, returning `None` if either component overflows
+    #[must_use]
+    pub fn checked_mul(&self, rhs: T) -> Option<Self> {
+        Some(Self::new(self.x.checked_mul(rhs)?, self.y.checked_mul(rhs)?))
     }
-}
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
-pub struct Point64 {
-    pub x: i64,
-    pub y: i64,
-}
+    /// Negates the point, returning `None` if either component is `T::MIN`
+    #[must_use]
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self::new(self.x.checked_neg()?, self.y.checked_neg()?))
+    }
 
-impl Point64 {
+    #[must_use]
+    pub fn saturating_add(&self, rhs: &Self) -> Self {
+        Self::new(self.x.saturating_add(rhs.x), self.y.saturating_add(rhs.y))
+    }
 
     #[must_use]
-    pub fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
+    pub fn saturating_sub(&self, rhs: &Self) -> Self {
+        Self::new(self.x.saturating_sub(rhs.x), self.y.saturating_sub(rhs.y))
     }
 
+    #[must_use]
+    pub fn saturating_mul(&self, rhs: T) -> Self {
+        Self::new(self.x.saturating_mul(rhs), self.y.saturating_mul(rhs))
+    }
+
+    /// Rotates this vector 90° clockwise around the origin.
+    /// The grid's y axis points down, so the vector for `Up` becomes `Right`.
+    #[must_use]
+    pub fn rotate_right(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates this vector 90° counter-clockwise around the origin.
+    /// The grid's y axis points down, so the vector for `Up` becomes `Left`.
+    #[must_use]
+    pub fn rotate_left(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    #[must_use]
+    pub fn rotate_180(&self) -> Self {
+        -*self
+    }
+
+    /// Rotates this point around `center` by a number of 90° clockwise turns.
+    /// Negative values turn counter-clockwise.
+    #[must_use]
+    pub fn rotate_around(&self, center: &Self, quarter_turns: i32) -> Self {
+        let offset = *self - *center;
+        let offset = match quarter_turns.rem_euclid(4) {
+            0 => offset,
+            1 => offset.rotate_right(),
+            2 => offset.rotate_180(),
+            _ => offset.rotate_left(),
+        };
+        *center + offset
+    }
+
+    /// Mirrors this point across the vertical line `x = axis`
+    #[must_use]
+    pub fn mirror_x(&self, axis: T) -> Self {
+        Self::new(axis + axis - self.x, self.y)
+    }
+
+    /// Mirrors this point across the horizontal line `y = axis`
+    #[must_use]
+    pub fn mirror_y(&self, axis: T) -> Self {
+        Self::new(self.x, axis + axis - self.y)
+    }
+
+    /// Mirrors this point across the diagonal `x = y` by swapping the components
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        Self::new(self.y, self.x)
+    }
+
+    #[must_use]
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3d cross product.
+    ///
+    /// With the y axis pointing down, a positive value means `other` is clockwise from `self`,
+    /// a negative value means counter-clockwise, and zero means the two are parallel.
+    #[must_use]
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl Point<i64> {
+
     #[must_use]
     pub fn from_point(point: Point) -> Self {
-        Self { x: i64::from(point.x), y: i64::from(point.y) }
+        Self::from(point)
     }
 
     #[must_use]
@@ -70,38 +172,281 @@ impl Point64 {
     }
 }
 
-impl Add<Point64> for Point64 {
-    type Output = Point64;
-    fn add(self, rhs: Point64) -> Point64 {
-        let x = self.x + rhs.x;
-        let y = self.y + rhs.y;
-        Self { x, y }
+impl<T: Coordinate> Add<Point<T>> for Point<T> {
+    type Output = Point<T>;
+    fn add(self, rhs: Point<T>) -> Self::Output {
+        Self { x: self.x + rhs.x, y: self.y + rhs.y }
     }
 }
 
-impl Add<i64> for Point64 {
-    type Output = Point64;
-    fn add(self, rhs: i64) -> Self::Output {
-        let x = self.x + rhs;
-        let y = self.y + rhs;
-        Self { x, y }
+impl<T: Coordinate> Add<T> for Point<T> {
+    type Output = Point<T>;
+    fn add(self, rhs: T) -> Self::Output {
+        Self { x: self.x + rhs, y: self.y + rhs }
     }
 }
 
-impl Sub<Point64> for Point64 {
-    type Output = Point64;
-    fn sub(self, rhs: Point64) -> Point64 {
-        let x = self.x - rhs.x;
-        let y = self.y - rhs.y;
-        Self { x, y }
+impl<T: Coordinate> AddAssign<Point<T>> for Point<T> {
+    fn add_assign(&mut self, rhs: Point<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
     }
 }
 
-impl Mul<i64> for Point64 {
-    type Output = Point64;
-    fn mul(self, rhs: i64) -> Self::Output {
-        let x = self.x * rhs;
-        let y = self.y * rhs;
-        Self { x, y }
+impl<T: Coordinate> AddAssign<T> for Point<T> {
+    fn add_assign(&mut self, rhs: T) {
+        self.x += rhs;
+        self.y += rhs;
+    }
+}
+
+impl<T: Coordinate> Sub<Point<T>> for Point<T> {
+    type Output = Point<T>;
+    fn sub(self, rhs: Point<T>) -> Self::Output {
+        Self { x: self.x - rhs.x, y: self.y - rhs.y }
+    }
+}
+
+impl<T: Coordinate> Sub<T> for Point<T> {
+    type Output = Point<T>;
+    fn sub(self, rhs: T) -> Self::Output {
+        Self { x: self.x - rhs, y: self.y - rhs }
+    }
+}
+
+impl<T: Coordinate> SubAssign<Point<T>> for Point<T> {
+    fn sub_assign(&mut self, rhs: Point<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Coordinate> SubAssign<T> for Point<T> {
+    fn sub_assign(&mut self, rhs: T) {
+        self.x -= rhs;
+        self.y -= rhs;
+    }
+}
+
+impl<T: Coordinate> Mul<T> for Point<T> {
+    type Output = Point<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self { x: self.x * rhs, y: self.y * rhs }
+    }
+}
+
+impl<T: Coordinate> Neg for Point<T> {
+    type Output = Point<T>;
+    fn neg(self) -> Self::Output {
+        Self { x: -self.x, y: -self.y }
+    }
+}
+
+/// Reading order: sorts top to bottom, then left to right
+impl<T: Coordinate> Ord for Point<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.y.cmp(&other.y).then(self.x.cmp(&other.x))
+    }
+}
+
+impl<T: Coordinate> PartialOrd for Point<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+/// Accepts `x,y`, `(x, y)` or `x y`
+impl<T: Coordinate> FromStr for Point<T> {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y] = parse_components(s)?;
+        Ok(Self { x, y })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePointError {
+    /// Opening and closing parentheses do not match up
+    UnbalancedParens,
+    WrongComponentCount { expected: usize, found: usize },
+    InvalidComponent { axis: char, value: String, error: ParseIntError },
+}
+
+impl Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnbalancedParens => write!(f, "unbalanced parentheses"),
+            Self::WrongComponentCount { expected, found } =>
+                write!(f, "expected {expected} components but found {found}"),
+            Self::InvalidComponent { axis, value, error } =>
+                write!(f, "invalid {axis} component '{value}': {error}"),
+        }
+    }
+}
+
+impl Error for ParsePointError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidComponent { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a point like `1,2,3`, `(1, 2, 3)` or `1 2 3` into its `N` components
+pub(crate) fn parse_components<T: Coordinate, const N: usize>(s: &str) -> Result<[T; N], ParsePointError> {
+    let s = s.trim();
+    let s = match (s.strip_prefix('('), s.strip_suffix(')')) {
+        (Some(_), Some(_)) => &s[1 .. s.len() - 1],
+        (None, None) => s,
+        _ => return Err(ParsePointError::UnbalancedParens),
+    };
+    let parts = if s.contains(',') {
+        s.split(',').map(str::trim).collect::<Vec<_>>()
+    } else {
+        s.split_whitespace().collect()
+    };
+    if parts.len() != N {
+        return Err(ParsePointError::WrongComponentCount { expected: N, found: parts.len() });
+    }
+    let mut components = [T::ZERO; N];
+    for (i, part) in parts.into_iter().enumerate() {
+        components[i] = part.parse().map_err(|error| ParsePointError::InvalidComponent {
+            axis: ['x', 'y', 'z'].get(i).copied().unwrap_or('?'),
+            value: part.to_string(),
+            error,
+        })?;
+    }
+    Ok(components)
+}
+
+// Widening conversions can never fail
+macro_rules! impl_point_from {
+    ($from:ty => $($to:ty),*) => {
+        $(
+            impl From<Point<$from>> for Point<$to> {
+                fn from(point: Point<$from>) -> Self {
+                    Self { x: point.x.into(), y: point.y.into() }
+                }
+            }
+        )*
+    };
+}
+
+impl_point_from!(i8 => i16, i32, i64, i128, isize);
+impl_point_from!(i16 => i32, i64, i128, isize);
+impl_point_from!(i32 => i64, i128);
+impl_point_from!(i64 => i128);
+
+// Narrowing conversions fail if either component does not fit
+macro_rules! impl_point_try_from {
+    ($from:ty => $($to:ty),*) => {
+        $(
+            impl TryFrom<Point<$from>> for Point<$to> {
+                type Error = std::num::TryFromIntError;
+                fn try_from(point: Point<$from>) -> Result<Self, Self::Error> {
+                    Ok(Self { x: point.x.try_into()?, y: point.y.try_into()? })
+                }
+            }
+        )*
+    };
+}
+
+impl_point_try_from!(i16 => i8);
+impl_point_try_from!(i32 => i8, i16, isize);
+impl_point_try_from!(i64 => i8, i16, i32, isize);
+impl_point_try_from!(i128 => i8, i16, i32, i64, isize);
+impl_point_try_from!(isize => i8, i16, i32, i64, i128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operators() {
+        let p = Point::new(3, -4);
+        assert_eq!(Point::new(4, -3), p + 1);
+        assert_eq!(Point::new(1, -1), p + Point::new(-2, 3));
+        assert_eq!(Point::new(6, -8), p * 2);
+        assert_eq!(Point::new(-3, 4), -p);
+        let mut q = Point64::new(10, 20);
+        q += Point64::new(1, 2);
+        q -= 5;
+        assert_eq!(Point64::new(6, 17), q);
+        assert_eq!(7, p.manhattan_distance(&Point::new(0, 0)));
+    }
+
+    #[test]
+    fn test_conversions() {
+        let p = Point::new(i32::MAX, -1);
+        let wide = Point64::from(p);
+        assert_eq!(Point64::new(i64::from(i32::MAX), -1), wide);
+        assert_eq!(wide, Point64::from_point(p));
+        assert_eq!(Ok(p), Point::try_from(wide));
+        assert!(Point::<i32>::try_from(wide + 1).is_err());
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let p = Point64::new(i64::MAX - 1, 0);
+        assert_eq!(Some(Point64::new(i64::MAX, 1)), p.checked_add(&Point64::new(1, 1)));
+        assert_eq!(None, p.checked_add(&Point64::new(2, 0)));
+        assert_eq!(None, p.checked_mul(2));
+        assert_eq!(None, Point64::new(i64::MIN, 0).checked_neg());
+        assert_eq!(Point64::new(i64::MAX, 1), p.saturating_add(&Point64::new(5, 1)));
+        assert_eq!(Point64::new(i64::MAX, 0), p.saturating_mul(3));
+        assert_eq!(Point::new(i32::MIN, -3), Point::new(i32::MIN + 1, 0).saturating_sub(&Point::new(5, 3)));
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(Ok(Point::new(3, -4)), "3,-4".parse());
+        assert_eq!(Ok(Point::new(3, -4)), " (3, -4) ".parse());
+        assert_eq!(Ok(Point64::new(3, -4)), "3 -4".parse());
+        assert_eq!("(3,-4)", Point::new(3, -4).to_string());
+        assert_eq!(Ok(Point::new(3, -4)), Point::new(3, -4).to_string().parse());
+
+        assert_eq!(Err(ParsePointError::UnbalancedParens), "(3,4".parse::<Point>());
+        assert_eq!(Err(ParsePointError::WrongComponentCount { expected: 2, found: 3 }), "1,2,3".parse::<Point>());
+        let err = "1,b".parse::<Point>().unwrap_err();
+        assert_eq!("invalid y component 'b': invalid digit found in string", err.to_string());
+    }
+
+    #[test]
+    fn test_reading_order() {
+        let mut points = vec![Point::new(2, 1), Point::new(0, 2), Point::new(1, 1), Point::new(5, 0)];
+        points.sort();
+        assert_eq!(vec![Point::new(5, 0), Point::new(1, 1), Point::new(2, 1), Point::new(0, 2)], points);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let p = Point::new(5, 2);
+        assert_eq!(p, p.rotate_right().rotate_left());
+        assert_eq!(p.rotate_180(), p.rotate_right().rotate_right());
+        assert_eq!(p, p.rotate_around(&Point::new(1, 1), 4));
+        // (5, 2) is 4 right and 1 down from the center, so a clockwise turn is 1 left and 4 down
+        assert_eq!(Point::new(0, 5), p.rotate_around(&Point::new(1, 1), 1));
+        assert_eq!(Point::new(0, 5), p.rotate_around(&Point::new(1, 1), -3));
+    }
+
+    #[test]
+    fn test_reflection_and_products() {
+        let p = Point64::new(5, 2);
+        assert_eq!(Point64::new(-3, 2), p.mirror_x(1));
+        assert_eq!(Point64::new(5, -2), p.mirror_y(0));
+        assert_eq!(Point64::new(2, 5), p.transpose());
+        assert_eq!(11, p.dot(&Point64::new(1, 3)));
+        assert_eq!(0, p.dot(&p.rotate_right()));
+        assert!(p.cross(&p.rotate_right()) > 0);
+        assert!(p.cross(&p.rotate_left()) < 0);
+        assert_eq!(0, p.cross(&(p * 3)));
     }
 }
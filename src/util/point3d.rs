@@ -0,0 +1,258 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use super::point::{parse_components, Coordinate, ParsePointError};
+
+/// A point in 3d space. Defaults to `i32` components, like [`super::point::Point`].
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash, Default)]
+pub struct Point3d<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+pub type Point3d64 = Point3d<i64>;
+
+impl<T: Coordinate> Point3d<T> {
+
+    #[must_use]
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    #[must_use]
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        (other.x - self.x).abs() + (other.y - self.y).abs() + (other.z - self.z).abs()
+    }
+
+    /// The largest distance along any single axis.
+    /// This is the number of moves it takes to get between points when diagonal moves are allowed.
+    #[must_use]
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        (other.x - self.x).abs()
+            .max((other.y - self.y).abs())
+            .max((other.z - self.z).abs())
+    }
+
+    /// Euclidean distance without the square root, so it stays an integer and is `Ord`.
+    /// Good enough for comparing which points are closer.
+    #[must_use]
+    pub fn squared_euclid_distance(&self, other: &Self) -> T {
+        let diff = *other - *self;
+        diff.x * diff.x + diff.y * diff.y + diff.z * diff.z
+    }
+
+    /// Component-wise minimum
+    #[must_use]
+    pub fn component_min(&self, other: &Self) -> Self {
+        Self::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    /// Component-wise maximum
+    #[must_use]
+    pub fn component_max(&self, other: &Self) -> Self {
+        Self::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+
+    /// Adds two points, returning `None` if any component overflows
+    #[must_use]
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(Self::new(self.x.checked_add(rhs.x)?, self.y.checked_add(rhs.y)?, self.z.checked_add(rhs.z)?))
+    }
+
+    /// Subtracts two points, returning `None` if any component overflows
+    #[must_use]
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(Self::new(self.x.checked_sub(rhs.x)?, self.y.checked_sub(rhs.y)?, self.z.checked_sub(rhs.z)?))
+    }
+
+    /// Scales the point, returning `None` if any component overflows
+    #[must_use]
+    pub fn checked_mul(&self, rhs: T) -> Option<Self> {
+        Some(Self::new(self.x.checked_mul(rhs)?, self.y.checked_mul(rhs)?, self.z.checked_mul(rhs)?))
+    }
+
+    #[must_use]
+    pub fn saturating_add(&self, rhs: &Self) -> Self {
+        Self::new(self.x.saturating_add(rhs.x), self.y.saturating_add(rhs.y), self.z.saturating_add(rhs.z))
+    }
+
+    #[must_use]
+    pub fn saturating_sub(&self, rhs: &Self) -> Self {
+        Self::new(self.x.saturating_sub(rhs.x), self.y.saturating_sub(rhs.y), self.z.saturating_sub(rhs.z))
+    }
+
+    #[must_use]
+    pub fn saturating_mul(&self, rhs: T) -> Self {
+        Self::new(self.x.saturating_mul(rhs), self.y.saturating_mul(rhs), self.z.saturating_mul(rhs))
+    }
+
+    /// The 6 points that share a face with this one
+    pub fn neighbors6(&self) -> impl Iterator<Item = Self> + use<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        let origin = *self;
+        [
+            Self::new(one, zero, zero),
+            Self::new(-one, zero, zero),
+            Self::new(zero, one, zero),
+            Self::new(zero, -one, zero),
+            Self::new(zero, zero, one),
+            Self::new(zero, zero, -one),
+        ].into_iter().map(move |delta| origin + delta)
+    }
+
+    /// The 26 points that share a face, edge or corner with this one
+    pub fn neighbors26(&self) -> impl Iterator<Item = Self> + use<T> {
+        let steps = [-T::ONE, T::ZERO, T::ONE];
+        let origin = *self;
+        steps.into_iter()
+            .flat_map(move |x| steps.into_iter()
+                .flat_map(move |y| steps.into_iter().map(move |z| Self::new(x, y, z))))
+            .filter(|&delta| delta != Self::default())
+            .map(move |delta| origin + delta)
+    }
+}
+
+impl<T: Coordinate> Add<Point3d<T>> for Point3d<T> {
+    type Output = Point3d<T>;
+    fn add(self, rhs: Point3d<T>) -> Self::Output {
+        Self { x: self.x + rhs.x, y: self.y + rhs.y, z: self.z + rhs.z }
+    }
+}
+
+impl<T: Coordinate> Add<T> for Point3d<T> {
+    type Output = Point3d<T>;
+    fn add(self, rhs: T) -> Self::Output {
+        Self { x: self.x + rhs, y: self.y + rhs, z: self.z + rhs }
+    }
+}
+
+impl<T: Coordinate> AddAssign<Point3d<T>> for Point3d<T> {
+    fn add_assign(&mut self, rhs: Point3d<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coordinate> Sub<Point3d<T>> for Point3d<T> {
+    type Output = Point3d<T>;
+    fn sub(self, rhs: Point3d<T>) -> Self::Output {
+        Self { x: self.x - rhs.x, y: self.y - rhs.y, z: self.z - rhs.z }
+    }
+}
+
+impl<T: Coordinate> Sub<T> for Point3d<T> {
+    type Output = Point3d<T>;
+    fn sub(self, rhs: T) -> Self::Output {
+        Self { x: self.x - rhs, y: self.y - rhs, z: self.z - rhs }
+    }
+}
+
+impl<T: Coordinate> SubAssign<Point3d<T>> for Point3d<T> {
+    fn sub_assign(&mut self, rhs: Point3d<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coordinate> Mul<T> for Point3d<T> {
+    type Output = Point3d<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self { x: self.x * rhs, y: self.y * rhs, z: self.z * rhs }
+    }
+}
+
+impl<T: Coordinate> Neg for Point3d<T> {
+    type Output = Point3d<T>;
+    fn neg(self) -> Self::Output {
+        Self { x: -self.x, y: -self.y, z: -self.z }
+    }
+}
+
+/// Reading order extended to 3d: sorts by z, then y, then x
+impl<T: Coordinate> Ord for Point3d<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.z.cmp(&other.z)
+            .then(self.y.cmp(&other.y))
+            .then(self.x.cmp(&other.x))
+    }
+}
+
+impl<T: Coordinate> PartialOrd for Point3d<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Display> Display for Point3d<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{},{})", self.x, self.y, self.z)
+    }
+}
+
+/// Accepts `x,y,z`, `(x, y, z)` or `x y z`
+impl<T: Coordinate> FromStr for Point3d<T> {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y, z] = parse_components(s)?;
+        Ok(Self { x, y, z })
+    }
+}
+
+impl From<Point3d<i32>> for Point3d<i64> {
+    fn from(point: Point3d<i32>) -> Self {
+        Self { x: point.x.into(), y: point.y.into(), z: point.z.into() }
+    }
+}
+
+impl TryFrom<Point3d<i64>> for Point3d<i32> {
+    type Error = std::num::TryFromIntError;
+    fn try_from(point: Point3d<i64>) -> Result<Self, Self::Error> {
+        Ok(Self { x: point.x.try_into()?, y: point.y.try_into()?, z: point.z.try_into()? })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        let a = Point3d64::new(162, 817, 812);
+        let b = Point3d64::new(425, 690, 689);
+        assert_eq!(263 + 127 + 123, a.manhattan_distance(&b));
+        assert_eq!(263, a.chebyshev_distance(&b));
+        assert_eq!(263 * 263 + 127 * 127 + 123 * 123, a.squared_euclid_distance(&b));
+        assert_eq!(Point3d64::new(162, 690, 689), a.component_min(&b));
+        assert_eq!(Point3d64::new(425, 817, 812), a.component_max(&b));
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let p = Point3d::new(i32::MAX, 0, i32::MIN);
+        assert_eq!(None, p.checked_add(&Point3d::new(1, 0, 0)));
+        assert_eq!(None, p.checked_sub(&Point3d::new(0, 0, 1)));
+        assert_eq!(Some(Point3d::new(i32::MAX, 1, i32::MIN)), p.checked_add(&Point3d::new(0, 1, 0)));
+        assert_eq!(Point3d::new(i32::MAX, 0, i32::MIN), p.saturating_mul(2));
+    }
+
+    #[test]
+    fn test_parse_display_and_order() {
+        let point: Point3d64 = "162,817,812".parse().unwrap();
+        assert_eq!(Point3d64::new(162, 817, 812), point);
+        assert_eq!(Ok(point), "(162, 817, 812)".parse());
+        assert_eq!("(162,817,812)", point.to_string());
+        assert!("162,817".parse::<Point3d64>().is_err());
+        assert!(Point3d::new(9, 9, 0) < Point3d::new(0, 0, 1));
+        assert!(Point3d::new(9, 0, 1) < Point3d::new(0, 1, 1));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let origin = Point3d::new(1, 2, 3);
+        assert_eq!(6, origin.neighbors6().count());
+        assert!(origin.neighbors6().all(|p| p.manhattan_distance(&origin) == 1));
+        assert_eq!(26, origin.neighbors26().count());
+        assert!(origin.neighbors26().all(|p| p.chebyshev_distance(&origin) == 1));
+    }
+}
@@ -0,0 +1,255 @@
+use std::array;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use super::point::{Coordinate, Point};
+use super::point3d::Point3d;
+
+/// A point with any number of dimensions, for puzzles that go beyond 3d.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub struct PointN<const N: usize, T = i32> {
+    pub coords: [T; N],
+}
+
+impl<const N: usize, T: Coordinate> PointN<N, T> {
+
+    #[must_use]
+    pub fn new(coords: [T; N]) -> Self {
+        Self { coords }
+    }
+
+    #[must_use]
+    pub fn origin() -> Self {
+        Self { coords: [T::ZERO; N] }
+    }
+
+    #[must_use]
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        self.zip(other, |a, b| (b - a).abs())
+            .coords.into_iter()
+            .fold(T::ZERO, |sum, d| sum + d)
+    }
+
+    /// The largest distance along any single axis
+    #[must_use]
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        self.zip(other, |a, b| (b - a).abs())
+            .coords.into_iter()
+            .fold(T::ZERO, T::max)
+    }
+
+    /// Euclidean distance without the square root, so it stays an integer and is `Ord`
+    #[must_use]
+    pub fn squared_euclid_distance(&self, other: &Self) -> T {
+        self.zip(other, |a, b| (b - a) * (b - a))
+            .coords.into_iter()
+            .fold(T::ZERO, |sum, d| sum + d)
+    }
+
+    /// Component-wise minimum
+    #[must_use]
+    pub fn component_min(&self, other: &Self) -> Self {
+        self.zip(other, T::min)
+    }
+
+    /// Component-wise maximum
+    #[must_use]
+    pub fn component_max(&self, other: &Self) -> Self {
+        self.zip(other, T::max)
+    }
+
+    /// Adds two points, returning `None` if any component overflows
+    #[must_use]
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        self.try_zip(rhs, T::checked_add)
+    }
+
+    /// Subtracts two points, returning `None` if any component overflows
+    #[must_use]
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        self.try_zip(rhs, T::checked_sub)
+    }
+
+    /// Scales the point, returning `None` if any component overflows
+    #[must_use]
+    pub fn checked_mul(&self, rhs: T) -> Option<Self> {
+        self.try_zip(&Self { coords: [rhs; N] }, T::checked_mul)
+    }
+
+    #[must_use]
+    pub fn saturating_add(&self, rhs: &Self) -> Self {
+        self.zip(rhs, T::saturating_add)
+    }
+
+    #[must_use]
+    pub fn saturating_sub(&self, rhs: &Self) -> Self {
+        self.zip(rhs, T::saturating_sub)
+    }
+
+    #[must_use]
+    pub fn saturating_mul(&self, rhs: T) -> Self {
+        Self { coords: self.coords.map(|a| a.saturating_mul(rhs)) }
+    }
+
+    /// The `2 * N` points one step away along a single axis
+    pub fn orthogonal_neighbors(&self) -> impl Iterator<Item = Self> + use<N, T> {
+        let origin = *self;
+        (0 .. N).flat_map(move |axis| [-T::ONE, T::ONE].into_iter().map(move |step| {
+            let mut next = origin;
+            next.coords[axis] += step;
+            next
+        }))
+    }
+
+    /// All `3^N - 1` points that touch this one, including diagonals
+    pub fn neighbors(&self) -> impl Iterator<Item = Self> + use<N, T> {
+        let origin = *self;
+        let total = 3_usize.pow(N as u32);
+        // count in base 3 where each digit is the offset (-1, 0, 1) for one axis
+        (0 .. total)
+            .filter(move |&n| n != (total - 1) / 2)
+            .map(move |mut n| {
+                let mut next = origin;
+                for coord in next.coords.iter_mut() {
+                    match n % 3 {
+                        0 => *coord -= T::ONE,
+                        2 => *coord += T::ONE,
+                        _ => (),
+                    }
+                    n /= 3;
+                }
+                next
+            })
+    }
+
+    fn zip(&self, other: &Self, f: impl Fn(T, T) -> T) -> Self {
+        Self { coords: array::from_fn(|i| f(self.coords[i], other.coords[i])) }
+    }
+
+    fn try_zip(&self, other: &Self, f: impl Fn(T, T) -> Option<T>) -> Option<Self> {
+        let mut coords = self.coords;
+        for (coord, &rhs) in coords.iter_mut().zip(&other.coords) {
+            *coord = f(*coord, rhs)?;
+        }
+        Some(Self { coords })
+    }
+}
+
+impl<const N: usize, T> Index<usize> for PointN<N, T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.coords[index]
+    }
+}
+
+impl<const N: usize, T> IndexMut<usize> for PointN<N, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.coords[index]
+    }
+}
+
+impl<const N: usize, T: Coordinate> Add<PointN<N, T>> for PointN<N, T> {
+    type Output = PointN<N, T>;
+    fn add(self, rhs: PointN<N, T>) -> Self::Output {
+        self.zip(&rhs, |a, b| a + b)
+    }
+}
+
+impl<const N: usize, T: Coordinate> Add<T> for PointN<N, T> {
+    type Output = PointN<N, T>;
+    fn add(self, rhs: T) -> Self::Output {
+        Self { coords: self.coords.map(|a| a + rhs) }
+    }
+}
+
+impl<const N: usize, T: Coordinate> AddAssign<PointN<N, T>> for PointN<N, T> {
+    fn add_assign(&mut self, rhs: PointN<N, T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize, T: Coordinate> Sub<PointN<N, T>> for PointN<N, T> {
+    type Output = PointN<N, T>;
+    fn sub(self, rhs: PointN<N, T>) -> Self::Output {
+        self.zip(&rhs, |a, b| a - b)
+    }
+}
+
+impl<const N: usize, T: Coordinate> Sub<T> for PointN<N, T> {
+    type Output = PointN<N, T>;
+    fn sub(self, rhs: T) -> Self::Output {
+        Self { coords: self.coords.map(|a| a - rhs) }
+    }
+}
+
+impl<const N: usize, T: Coordinate> SubAssign<PointN<N, T>> for PointN<N, T> {
+    fn sub_assign(&mut self, rhs: PointN<N, T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize, T: Coordinate> Mul<T> for PointN<N, T> {
+    type Output = PointN<N, T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self { coords: self.coords.map(|a| a * rhs) }
+    }
+}
+
+impl<const N: usize, T: Coordinate> Neg for PointN<N, T> {
+    type Output = PointN<N, T>;
+    fn neg(self) -> Self::Output {
+        Self { coords: self.coords.map(|a| -a) }
+    }
+}
+
+impl<T> From<Point<T>> for PointN<2, T> {
+    fn from(point: Point<T>) -> Self {
+        Self { coords: [point.x, point.y] }
+    }
+}
+
+impl<T> From<Point3d<T>> for PointN<3, T> {
+    fn from(point: Point3d<T>) -> Self {
+        Self { coords: [point.x, point.y, point.z] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        let a = PointN::new([0, 3, -2, 5]);
+        let b = PointN::new([1, -1, 4, 5]);
+        assert_eq!(11, a.manhattan_distance(&b));
+        assert_eq!(6, a.chebyshev_distance(&b));
+        assert_eq!(1 + 16 + 36, a.squared_euclid_distance(&b));
+        assert_eq!(PointN::new([0, -1, -2, 5]), a.component_min(&b));
+        assert_eq!(PointN::new([1, 3, 4, 5]), a.component_max(&b));
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let p = PointN::new([i32::MAX, 0, i32::MIN, 1]);
+        assert_eq!(None, p.checked_add(&PointN::new([1, 0, 0, 0])));
+        assert_eq!(None, p.checked_sub(&PointN::new([0, 0, 1, 0])));
+        assert_eq!(None, p.checked_mul(2));
+        assert_eq!(Some(PointN::new([i32::MAX, 1, i32::MIN, 2])), p.checked_add(&PointN::new([0, 1, 0, 1])));
+        assert_eq!(PointN::new([i32::MAX, 0, i32::MIN, 2]), p.saturating_mul(2));
+        assert_eq!(PointN::new([i32::MAX, -1, i32::MIN, 0]), p.saturating_sub(&PointN::new([-5, 1, 5, 1])));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let origin = PointN::<4>::origin();
+        assert_eq!(8, origin.orthogonal_neighbors().count());
+        assert_eq!(80, origin.neighbors().count());
+        assert!(origin.neighbors().all(|p| p.chebyshev_distance(&origin) == 1));
+
+        let point = Point3d::new(1, 2, 3);
+        let mut expected = point.neighbors26().map(PointN::from).collect::<Vec<_>>();
+        let mut actual = PointN::from(point).neighbors().collect::<Vec<_>>();
+        expected.sort_by_key(|p| p.coords);
+        actual.sort_by_key(|p| p.coords);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,317 @@
+use super::point::{Coordinate, Point};
+use super::point3d::Point3d;
+use super::vec2d::Vec2d;
+
+/// An axis aligned rectangle. Both corners are inside the rectangle.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub struct Rect<T = i32> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Coordinate> Rect<T> {
+
+    /// Builds the rectangle with `a` and `b` as opposite corners, in any order
+    #[must_use]
+    pub fn from_corners(a: Point<T>, b: Point<T>) -> Self {
+        Self {
+            min: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// The smallest rectangle containing every point, or `None` if there are no points
+    pub fn from_points(points: impl IntoIterator<Item = Point<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut rect = Self::from_corners(first, first);
+        points.for_each(|point| rect.include(point));
+        Some(rect)
+    }
+
+    /// Grows the rectangle just enough to contain `point`
+    pub fn include(&mut self, point: Point<T>) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    /// Grows (or shrinks for negative values) the rectangle by `amount` on every side
+    #[must_use]
+    pub fn expand(&self, amount: T) -> Self {
+        Self { min: self.min - amount, max: self.max + amount }
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point<T>) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x
+            && point.y >= self.min.y && point.y <= self.max.y
+    }
+
+    #[must_use]
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    /// The overlapping area, or `None` if the rectangles do not overlap
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        (min.x <= max.x && min.y <= max.y).then_some(Self { min, max })
+    }
+
+    /// The smallest rectangle containing both rectangles
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut rect = *self;
+        rect.include(other.min);
+        rect.include(other.max);
+        rect
+    }
+
+    /// Number of columns, counting both edges
+    #[must_use]
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    /// Number of rows, counting both edges
+    #[must_use]
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    /// Number of points inside the rectangle, including the edges
+    #[must_use]
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    /// Distance from the left edge to the right edge
+    #[must_use]
+    pub fn exclusive_width(&self) -> T {
+        self.max.x - self.min.x
+    }
+
+    /// Distance from the top edge to the bottom edge
+    #[must_use]
+    pub fn exclusive_height(&self) -> T {
+        self.max.y - self.min.y
+    }
+
+    /// Geometric area when the corners are treated as points rather than cells
+    #[must_use]
+    pub fn exclusive_area(&self) -> T {
+        self.exclusive_width() * self.exclusive_height()
+    }
+
+    /// Every point in the rectangle in reading order
+    pub fn points(&self) -> impl Iterator<Item = Point<T>> + use<T> {
+        let Self { min, max } = *self;
+        let mut next = (min.x <= max.x && min.y <= max.y).then_some(min);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = if current.x < max.x {
+                Some(Point::new(current.x + T::ONE, current.y))
+            } else if current.y < max.y {
+                Some(Point::new(min.x, current.y + T::ONE))
+            } else {
+                None
+            };
+            Some(current)
+        })
+    }
+}
+
+impl Rect<i32> {
+
+    /// Converts a point in this rectangle to the matching point in a [`Vec2d`]
+    /// built from [`Self::to_vec2d`], where `min` is the origin.
+    #[must_use]
+    pub fn to_local(&self, point: Point) -> Point {
+        point - self.min
+    }
+
+    /// The inverse of [`Self::to_local`]
+    #[must_use]
+    pub fn to_global(&self, point: Point) -> Point {
+        point + self.min
+    }
+
+    /// A grid with one cell for every point in the rectangle
+    ///
+    /// # Panics
+    /// If the rectangle is empty
+    #[must_use]
+    pub fn to_vec2d<T: Clone>(&self, value: T) -> Vec2d<T> {
+        Vec2d::filled(self.width(), self.height(), value)
+    }
+}
+
+impl<T: Clone> From<&Vec2d<T>> for Rect {
+    fn from(grid: &Vec2d<T>) -> Self {
+        grid.bounds()
+    }
+}
+
+/// An axis aligned box in 3d. Both corners are inside the cuboid.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub struct Cuboid<T = i32> {
+    pub min: Point3d<T>,
+    pub max: Point3d<T>,
+}
+
+impl<T: Coordinate> Cuboid<T> {
+
+    /// Builds the cuboid with `a` and `b` as opposite corners, in any order
+    #[must_use]
+    pub fn from_corners(a: Point3d<T>, b: Point3d<T>) -> Self {
+        Self { min: a.component_min(&b), max: a.component_max(&b) }
+    }
+
+    /// The smallest cuboid containing every point, or `None` if there are no points
+    pub fn from_points(points: impl IntoIterator<Item = Point3d<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut cuboid = Self::from_corners(first, first);
+        points.for_each(|point| cuboid.include(point));
+        Some(cuboid)
+    }
+
+    /// Grows the cuboid just enough to contain `point`
+    pub fn include(&mut self, point: Point3d<T>) {
+        self.min = self.min.component_min(&point);
+        self.max = self.max.component_max(&point);
+    }
+
+    /// Grows (or shrinks for negative values) the cuboid by `amount` on every side
+    #[must_use]
+    pub fn expand(&self, amount: T) -> Self {
+        Self { min: self.min - amount, max: self.max + amount }
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point3d<T>) -> bool {
+        self.min.component_min(&point) == self.min && self.max.component_max(&point) == self.max
+    }
+
+    /// The overlapping volume, or `None` if the cuboids do not overlap
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = self.min.component_max(&other.min);
+        let max = self.max.component_min(&other.max);
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Self { min, max })
+    }
+
+    /// The smallest cuboid containing both cuboids
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self { min: self.min.component_min(&other.min), max: self.max.component_max(&other.max) }
+    }
+
+    /// Size along x, counting both faces
+    #[must_use]
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    /// Size along y, counting both faces
+    #[must_use]
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    /// Size along z, counting both faces
+    #[must_use]
+    pub fn depth(&self) -> T {
+        self.max.z - self.min.z + T::ONE
+    }
+
+    /// Number of points inside the cuboid, including the faces
+    #[must_use]
+    pub fn volume(&self) -> T {
+        self.width() * self.height() * self.depth()
+    }
+
+    /// Geometric volume when the corners are treated as points rather than cubes
+    #[must_use]
+    pub fn exclusive_volume(&self) -> T {
+        let size = self.max - self.min;
+        size.x * size.y * size.z
+    }
+
+    /// Every point in the cuboid, ordered by z, then y, then x
+    pub fn points(&self) -> impl Iterator<Item = Point3d<T>> + use<T> {
+        let Self { min, max } = *self;
+        let mut next = (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(min);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = if current.x < max.x {
+                Some(Point3d::new(current.x + T::ONE, current.y, current.z))
+            } else if current.y < max.y {
+                Some(Point3d::new(min.x, current.y + T::ONE, current.z))
+            } else if current.z < max.z {
+                Some(Point3d::new(min.x, min.y, current.z + T::ONE))
+            } else {
+                None
+            };
+            Some(current)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect() {
+        let rect = Rect::from_corners(Point::new(4, 1), Point::new(1, 3));
+        assert_eq!(Point::new(1, 1), rect.min);
+        assert_eq!(Point::new(4, 3), rect.max);
+        assert_eq!((4, 3, 12), (rect.width(), rect.height(), rect.area()));
+        assert_eq!((3, 2, 6), (rect.exclusive_width(), rect.exclusive_height(), rect.exclusive_area()));
+        assert!(rect.contains(Point::new(4, 3)) && !rect.contains(Point::new(5, 3)));
+
+        let other = Rect::from_corners(Point::new(3, 0), Point::new(8, 1));
+        assert_eq!(Some(Rect::from_corners(Point::new(3, 1), Point::new(4, 1))), rect.intersection(&other));
+        assert_eq!(Rect::from_corners(Point::new(1, 0), Point::new(8, 3)), rect.union(&other));
+        assert_eq!(None, rect.intersection(&Rect::from_corners(Point::new(5, 5), Point::new(6, 6))));
+
+        let points = rect.points().collect::<Vec<_>>();
+        assert_eq!(12, points.len());
+        assert!(points.is_sorted());
+        assert_eq!(Some(rect), Rect::from_points(points));
+        assert_eq!(None, Rect::<i32>::from_points([]));
+    }
+
+    #[test]
+    fn test_vec2d_bounds() {
+        let rect = Rect::from_corners(Point::new(-2, 5), Point::new(3, 7));
+        let grid = rect.to_vec2d(0);
+        assert_eq!(18, grid.grid.len());
+        assert_eq!(Point::new(0, 0), rect.to_local(rect.min));
+        assert!(grid.in_bounds(rect.to_local(rect.max)));
+        assert!(!grid.in_bounds(rect.to_local(rect.max + 1)));
+        assert_eq!(Rect::from_corners(Point::new(0, 0), Point::new(5, 2)), Rect::from(&grid));
+        assert_eq!(rect.max, rect.to_global(grid.bounds().max));
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let cuboid = Cuboid::from_corners(Point3d::new(2, 0, 1), Point3d::new(0, 1, 3));
+        assert_eq!(18, cuboid.volume());
+        assert_eq!(4, cuboid.exclusive_volume());
+        assert!(cuboid.contains(Point3d::new(1, 1, 2)) && !cuboid.contains(Point3d::new(1, 2, 2)));
+        assert_eq!(18, cuboid.points().count());
+        assert_eq!(Some(cuboid), Cuboid::from_points(cuboid.points()));
+        let other = Cuboid::from_corners(Point3d::new(2, 1, 3), Point3d::new(5, 5, 5));
+        assert_eq!(Some(Cuboid::from_corners(Point3d::new(2, 1, 3), Point3d::new(2, 1, 3))), cuboid.intersection(&other));
+        assert_eq!(Cuboid::from_corners(Point3d::new(0, 0, 1), Point3d::new(5, 5, 5)), cuboid.union(&other));
+        assert!(cuboid.points().is_sorted());
+
+        // stops at the edge of the type instead of overflowing
+        let at_max = Cuboid::from_corners(Point3d::new(i32::MAX - 1, i32::MAX, i32::MAX - 1), Point3d::new(i32::MAX, i32::MAX, i32::MAX));
+        assert_eq!(4, at_max.points().count());
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display, Write};
+use super::point::Point;
+use super::rect::Rect;
+use super::vec2d::Vec2d;
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn ansi_code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+/// How a highlighted point is drawn
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Highlight {
+    /// Replace the cell with this character
+    Marker(char),
+    /// Keep the cell's character but draw it in a terminal color
+    Color(Color),
+}
+
+/// Draws a grid as text. Build one with [`Vec2d::render`], add any highlights or rulers,
+/// then print it or call `to_string()`.
+pub struct Renderer<'a> {
+    bounds: Rect,
+    cell: Box<dyn Fn(Point) -> char + 'a>,
+    highlights: Vec<(HashSet<Point>, Highlight)>,
+    rulers: bool,
+}
+
+impl<'a> Renderer<'a> {
+
+    /// Renders every point in `bounds`, using `cell` to pick the character for each point
+    pub fn new(bounds: Rect, cell: impl Fn(Point) -> char + 'a) -> Self {
+        Self { bounds, cell: Box::new(cell), highlights: vec![], rulers: false }
+    }
+
+    /// Draws these points differently. When highlights overlap, the last one added wins.
+    #[must_use]
+    pub fn highlight(mut self, points: impl IntoIterator<Item = Point>, highlight: Highlight) -> Self {
+        self.highlights.push((points.into_iter().collect(), highlight));
+        self
+    }
+
+    /// Adds column numbers above the grid and row numbers to its left.
+    /// Negative columns get an extra line on top with a `-` above them.
+    #[must_use]
+    pub fn rulers(mut self, rulers: bool) -> Self {
+        self.rulers = rulers;
+        self
+    }
+
+    fn write_column_rulers(&self, f: &mut fmt::Formatter<'_>, label_width: usize) -> fmt::Result {
+        let Rect { min, max } = self.bounds;
+        let widest = min.x.unsigned_abs().max(max.x.unsigned_abs());
+        let digits = widest.checked_ilog10().unwrap_or(0) + 1;
+        if min.x < 0 {
+            write!(f, "{:label_width$} ", "")?;
+            for x in min.x ..= max.x {
+                f.write_char(if x < 0 { '-' } else { ' ' })?;
+            }
+            writeln!(f)?;
+        }
+        // one line per digit, most significant first, read top to bottom
+        for place in (0 .. digits).rev() {
+            write!(f, "{:label_width$} ", "")?;
+            for x in min.x ..= max.x {
+                let digit = (x.unsigned_abs() / 10_u32.pow(place)) % 10;
+                f.write_char(char::from_digit(digit, 10).unwrap_or('?'))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Renderer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Rect { min, max } = self.bounds;
+        let label_width = min.y.to_string().len().max(max.y.to_string().len());
+        if self.rulers {
+            self.write_column_rulers(f, label_width)?;
+        }
+        for y in min.y ..= max.y {
+            if self.rulers {
+                write!(f, "{y:>label_width$} ")?;
+            }
+            for x in min.x ..= max.x {
+                let point = Point::new(x, y);
+                let c = (self.cell)(point);
+                let highlight = self.highlights.iter().rev()
+                    .find(|(points, _)| points.contains(&point))
+                    .map(|(_, highlight)| *highlight);
+                match highlight {
+                    Some(Highlight::Marker(marker)) => f.write_char(marker)?,
+                    Some(Highlight::Color(color)) => write!(f, "\x1b[{}m{c}\x1b[0m", color.ansi_code())?,
+                    None => f.write_char(c)?,
+                }
+            }
+            if y < max.y {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Clone> Vec2d<T> {
+
+    /// Draws the grid as text, using `cell` to turn each value into a character.
+    /// Rows are separated by `\n` with no newline after the last row.
+    pub fn render<'a>(&'a self, cell: impl Fn(&T) -> char + 'a) -> Renderer<'a> {
+        Renderer::new(self.bounds(), move |point| cell(&self[point]))
+    }
+}
+
+impl<T: Clone + Display> Display for Vec2d<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.grid.chunks(self.line_len as usize).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@";
+
+    #[test]
+    fn test_round_trip() {
+        let grid = Vec2d::parse(TEST, |c| c).unwrap();
+        assert_eq!(TEST, grid.to_string());
+        assert_eq!(TEST, grid.render(|&c| c).to_string());
+
+        let bools = Vec2d::parse(TEST, |c| c == '@').unwrap();
+        assert_eq!(TEST, bools.render(|&paper| if paper { '@' } else { '.' }).to_string());
+    }
+
+    #[test]
+    fn test_highlights() {
+        let grid = Vec2d::parse(TEST, |c| c).unwrap();
+        let rendered = grid.render(|&c| c)
+            .highlight([Point::new(0, 0), Point::new(1, 0)], Highlight::Marker('x'))
+            .highlight([Point::new(1, 0)], Highlight::Marker('o'))
+            .highlight([Point::new(2, 2)], Highlight::Color(Color::Red))
+            .to_string();
+        let expected = "xo@@.@@@@.\n@@@.@.@.@@\n@@\x1b[31m@\x1b[0m@@.@.@@";
+        assert_eq!(expected, rendered);
+    }
+
+    #[test]
+    fn test_rulers() {
+        let grid = Vec2d::filled(12, 2, '.');
+        let expected = "  000000000011
+  012345678901
+0 ............
+1 ............";
+        assert_eq!(expected, grid.render(|&c| c).rulers(true).to_string());
+
+        // columns -3 and 3 share their digits, so only the sign line tells them apart
+        let bounds = Rect::from_corners(Point::new(-3, -1), Point::new(3, 0));
+        let expected = "   ---    
+   3210123
+-1 .......
+ 0 .......";
+        assert_eq!(expected, Renderer::new(bounds, |_| '.').rulers(true).to_string());
+    }
+}
@@ -0,0 +1,377 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use super::grid::Grid;
+use super::point::Point;
+use super::vec2d::Directions;
+
+/// Everything a search found: the distance to every state it reached,
+/// and how to get there along any of the shortest paths.
+///
+/// States can be anything hashable, so a search can track more than a position,
+/// for example `(Point, Directions)` when turning has a cost.
+#[derive(Clone, Debug)]
+pub struct Search<S> {
+    /// Shortest known distance from the start to each state that was reached
+    pub distances: HashMap<S, usize>,
+    /// The first goal state reached, if any
+    pub goal: Option<S>,
+    // every state that comes right before each state on some shortest path
+    predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+
+    fn new(start: S) -> Self {
+        let mut distances = HashMap::new();
+        distances.insert(start.clone(), 0);
+        let mut predecessors = HashMap::new();
+        predecessors.insert(start, vec![]);
+        Self { distances, goal: None, predecessors }
+    }
+
+    /// Records that `next` can be reached from `from` at a total cost of `distance`.
+    /// Returns true if this is a new shortest distance.
+    fn relax(&mut self, from: &S, next: S, distance: usize) -> bool {
+        match self.distances.get(&next) {
+            Some(&known) if distance > known => false,
+            Some(&known) if distance == known => {
+                // a step that costs 0 could lead back to one of `from`'s own predecessors,
+                // which would make paths loop forever
+                if self.distances[from] < distance || !self.leads_to(&next, from) {
+                    self.predecessors.entry(next).or_default().push(from.clone());
+                }
+                false
+            }
+            _ => {
+                self.distances.insert(next.clone(), distance);
+                self.predecessors.insert(next, vec![from.clone()]);
+                true
+            }
+        }
+    }
+
+    /// Whether `state` is on a recorded shortest path to `target`.
+    /// Only states at the same distance as `state` are followed, since those are the only ones a step costing 0 can reach.
+    fn leads_to(&self, state: &S, target: &S) -> bool {
+        let distance = self.distances[state];
+        let mut stack = vec![target];
+        let mut seen = HashSet::new();
+        while let Some(current) = stack.pop() {
+            if current == state {
+                return true;
+            }
+            if seen.insert(current) {
+                stack.extend(self.predecessors[current].iter().filter(|&p| self.distances[p] == distance));
+            }
+        }
+        false
+    }
+
+    #[must_use]
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.distances.get(state).copied()
+    }
+
+    /// Distance from the start to the goal
+    #[must_use]
+    pub fn goal_distance(&self) -> Option<usize> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    /// One shortest path from the start to `state`, including both ends
+    #[must_use]
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        let mut path = vec![state.clone()];
+        let mut current = state;
+        while let Some(previous) = self.predecessors.get(current)?.first() {
+            path.push(previous.clone());
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// One shortest path from the start to the goal
+    #[must_use]
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// Every path from the start to `state` that is as short as the shortest one
+    #[must_use]
+    pub fn all_paths_to(&self, state: &S) -> Vec<Vec<S>> {
+        let Some(previous) = self.predecessors.get(state) else {
+            return vec![];
+        };
+        if previous.is_empty() {
+            return vec![vec![state.clone()]];
+        }
+        previous.iter()
+            .flat_map(|p| self.all_paths_to(p))
+            .map(|mut path| {
+                path.push(state.clone());
+                path
+            })
+            .collect()
+    }
+
+    /// Every shortest path from the start to the goal
+    #[must_use]
+    pub fn all_paths(&self) -> Vec<Vec<S>> {
+        self.goal.as_ref().map(|goal| self.all_paths_to(goal)).unwrap_or_default()
+    }
+}
+
+/// Breadth first search where every step costs 1.
+///
+/// Stops once a goal state is reached. If no state is a goal, every reachable state is explored.
+pub fn bfs<S, I>(start: S, mut successors: impl FnMut(&S) -> I, mut is_goal: impl FnMut(&S) -> bool) -> Search<S>
+    where S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+    // Every state one step closer than the goal is dequeued before it,
+    // so all of the goal's shortest path predecessors are known by the time it comes up
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        let distance = search.distances[&state] + 1;
+        for next in successors(&state) {
+            if search.relax(&state, next.clone(), distance) {
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm. `successors` returns each next state along with the cost of the step.
+/// Steps may cost 0, but a path never visits the same state twice.
+///
+/// Stops once a goal state is reached. If no state is a goal, every reachable state is explored.
+pub fn dijkstra<S, I>(start: S, successors: impl FnMut(&S) -> I, is_goal: impl FnMut(&S) -> bool) -> Search<S>
+    where S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    astar(start, successors, |_| 0, is_goal)
+}
+
+/// A* search. `heuristic` estimates the remaining cost to a goal and must never overestimate it,
+/// or the path found may not be the shortest.
+pub fn astar<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+    where S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut search = Search::new(start.clone());
+    // the heap holds indexes into `states` so that states don't need to be Ord
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut states = vec![start];
+    let mut goal_estimate = None;
+    while let Some(Reverse((estimate, distance, idx))) = heap.pop() {
+        // Keep going until everything that could tie the goal's distance has been seen,
+        // so that all of the shortest paths are recorded
+        if goal_estimate.is_some_and(|goal_estimate| estimate > goal_estimate) {
+            break;
+        }
+        let state = states[idx].clone();
+        if search.distances[&state] < distance {
+            continue;
+        }
+        if is_goal(&state) {
+            if search.goal.is_none() {
+                search.goal = Some(state);
+                goal_estimate = Some(estimate);
+            }
+            continue;
+        }
+        for (next, cost) in successors(&state) {
+            let next_distance = distance + cost;
+            if search.relax(&state, next.clone(), next_distance) {
+                heap.push(Reverse((next_distance + heuristic(&next), next_distance, states.len())));
+                states.push(next);
+            }
+        }
+    }
+    search
+}
+
+/// Searches between points on any [`Grid`], moving up, down, left or right.
+/// Grids with no edge, like [`SparseGrid`], must have their steps limited by the closure
+/// or the goal might never be found.
+///
+/// [`SparseGrid`]: super::sparse::SparseGrid
+pub trait GridSearch: Grid {
+
+    /// Shortest path between two points. `passable` decides whether each step `(from, to, direction)` can be taken.
+    fn bfs(&self, start: Point, goal: Point, passable: impl Fn(Point, Point, Directions) -> bool) -> Search<Point> {
+        bfs(
+            start,
+            |&from| self.neighbors4(from)
+                .filter(|&(direction, to, _)| passable(from, to, direction))
+                .map(|(_, to, _)| to)
+                .collect::<Vec<_>>(),
+            |&point| point == goal,
+        )
+    }
+
+    /// Cheapest path between two points.
+    /// `cost` gives the cost of each step `(from, to, direction)`, or `None` if it can't be taken.
+    fn dijkstra(&self, start: Point, goal: Point, cost: impl Fn(Point, Point, Directions) -> Option<usize>) -> Search<Point> {
+        dijkstra(start, |&from| weighted_neighbors(self, from, &cost), |&point| point == goal)
+    }
+
+    /// Same as [`Self::dijkstra`], guided by the Manhattan distance to the goal.
+    /// Every step must cost at least 1 for the result to be the cheapest path.
+    /// Steps across the edge of a wrapping grid can be shorter than the Manhattan distance,
+    /// so use [`Self::dijkstra`] on those.
+    fn astar(&self, start: Point, goal: Point, cost: impl Fn(Point, Point, Directions) -> Option<usize>) -> Search<Point> {
+        astar(
+            start,
+            |&from| weighted_neighbors(self, from, &cost),
+            |point| point.manhattan_distance(&goal) as usize,
+            |&point| point == goal,
+        )
+    }
+}
+
+impl<G: Grid> GridSearch for G {}
+
+fn weighted_neighbors<G: Grid + ?Sized>(grid: &G, from: Point, cost: impl Fn(Point, Point, Directions) -> Option<usize>) -> Vec<(Point, usize)> {
+    grid.neighbors4(from)
+        .filter_map(|(direction, to, _)| cost(from, to, direction).map(|c| (to, c)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::sparse::SparseGrid;
+    use crate::util::vec2d::{EdgePolicy, Vec2d};
+
+    const MAZE: &str = "#########
+#S..#...#
+#.#.#.#.#
+#.......#
+#.#.#.#E#
+#########";
+
+    fn maze() -> (Vec2d<char>, Point, Point) {
+        let grid = Vec2d::parse(MAZE, |c| c).unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+        (grid, start, end)
+    }
+
+    #[test]
+    fn test_bfs() {
+        let (grid, start, end) = maze();
+        let search = grid.bfs(start, end, |_, to, _| grid[to] != '#');
+        assert_eq!(Some(9), search.goal_distance());
+        let path = search.path().unwrap();
+        assert_eq!(10, path.len());
+        assert_eq!((start, end), (path[0], path[9]));
+        assert!(path.windows(2).all(|step| step[0].manhattan_distance(&step[1]) == 1));
+        // down the left or down the middle, then along the bottom corridor
+        assert_eq!(2, search.all_paths().len());
+        assert!(search.all_paths().iter().all(|path| path.len() == 10));
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        // a wall from (0, -2) to (0, 2) with open space around it
+        let walls = (-2 ..= 2).map(|y| (Point::new(0, y), true)).collect::<SparseGrid<bool>>();
+        let area = walls.bounds().expand(1);
+        let search = walls.bfs(Point::new(-1, 0), Point::new(1, 0), |_, to, _| area.contains(to) && !walls[to]);
+        assert_eq!(Some(8), search.goal_distance());
+        assert_eq!(2, search.all_paths().len());
+    }
+
+    #[test]
+    fn test_zero_cost_steps() {
+        // 0 and 1 are connected both ways for free, and 2 has a free step to itself
+        let edges = |&state: &u32| match state {
+            0 => vec![(1, 0), (2, 1), (0, 0)],
+            1 => vec![(0, 0), (2, 1)],
+            _ => vec![(2, 0)],
+        };
+        let search = dijkstra(0, edges, |&state| state == 2);
+        assert_eq!(Some(1), search.goal_distance());
+        assert_eq!(Some(vec![0, 2]), search.path());
+        assert_eq!(Some(vec![0]), search.path_to(&0));
+        let mut paths = search.all_paths();
+        paths.sort();
+        assert_eq!(vec![vec![0, 1, 2], vec![0, 2]], paths);
+    }
+
+    #[test]
+    fn test_wrapping_grid() {
+        // the wall down the middle can only be passed by wrapping around the sides
+        let grid = Vec2d::parse(".#.\n.#.\n.#.", |c| c).unwrap().with_edges(EdgePolicy::Wrap);
+        let (start, end) = (Point::new(0, 1), Point::new(2, 1));
+        let search = grid.bfs(start, end, |_, to, _| grid[to] != '#');
+        assert_eq!(Some(1), search.goal_distance());
+        assert_eq!(Some(vec![start, end]), search.path());
+
+        let bounded = Vec2d::parse(".#.\n.#.\n.#.", |c| c).unwrap();
+        assert_eq!(None, bounded.bfs(start, end, |_, to, _| bounded[to] != '#').goal_distance());
+    }
+
+    #[test]
+    fn test_default_edges_stay_on_grid() {
+        // the open default value outside the grid doesn't give a way around the wall
+        let grid = Vec2d::parse(".#.\n.#.\n.#.", |c| c).unwrap().with_edges(EdgePolicy::Default('.'));
+        let (start, end) = (Point::new(0, 1), Point::new(2, 1));
+        assert_eq!(None, grid.bfs(start, end, |_, to, _| grid[to] != '#').goal_distance());
+        let search = grid.dijkstra(start, end, |_, to, _| (grid[to] != '#').then_some(1));
+        assert_eq!(None, search.goal_distance());
+        assert_eq!(3, search.distances.len());
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let (grid, start, end) = maze();
+        // stepping onto row 3 is expensive, so cutting back up through column 5 costs the same
+        // as staying on row 3
+        let cost = |_, to: Point, _| match grid[to] {
+            '#' => None,
+            _ if to.y == 3 => Some(5),
+            _ => Some(1),
+        };
+        let dijkstra = grid.dijkstra(start, end, cost);
+        let astar = grid.astar(start, end, cost);
+        assert_eq!(Some(29), dijkstra.goal_distance());
+        assert_eq!(2, dijkstra.all_paths().len());
+        assert_eq!(dijkstra.goal_distance(), astar.goal_distance());
+        assert_eq!(dijkstra.all_paths().len(), astar.all_paths().len());
+        assert!(astar.distances.len() <= dijkstra.distances.len());
+    }
+
+    #[test]
+    fn test_state_with_facing() {
+        // moving forward costs 1 and turning 90 degrees costs 1000
+        let (grid, start, end) = maze();
+        let search = dijkstra(
+            (start, Directions::Right),
+            |&(point, facing)| {
+                let mut next = vec![((point, facing.turn_right()), 1000), ((point, facing.turn_left()), 1000)];
+                if let Some(forward) = grid.next_point(point, facing).filter(|&p| grid[p] != '#') {
+                    next.push(((forward, facing), 1));
+                }
+                next
+            },
+            |&(point, _)| point == end,
+        );
+        // 9 steps with at least 3 turns: right, down, right, down
+        assert_eq!(Some(3009), search.goal_distance());
+        assert_eq!(Some(end), search.goal.map(|(point, _)| point));
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::ops::Index;
+use super::grid::Grid;
+use super::point::Point;
+use super::rect::Rect;
+
+/// A grid with no fixed size, storing only the cells that have been set.
+/// Every other point reads as the default value, including points at negative coordinates.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    default: T,
+    // covers every stored cell, None when nothing is stored
+    bounds: Option<Rect>,
+}
+
+impl<T: Clone> SparseGrid<T> {
+
+    #[must_use]
+    pub fn new(default: T) -> Self {
+        Self { cells: HashMap::new(), default, bounds: None }
+    }
+
+    #[must_use]
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// Stores `value` at `point`, returning the value that was stored there before
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(point),
+            None => self.bounds = Some(Rect::from_corners(point, point)),
+        }
+        self.cells.insert(point, value)
+    }
+
+    /// Removes the value stored at `point` so it reads as the default again
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let removed = self.cells.remove(&point)?;
+        // bounds only need recomputing when the point was on their edge
+        if self.bounds.is_some_and(|Rect { min, max }| point.x == min.x || point.y == min.y || point.x == max.x || point.y == max.y) {
+            self.bounds = Rect::from_points(self.cells.keys().copied());
+        }
+        Some(removed)
+    }
+
+    /// A mutable reference to the value at `point`, storing the default value there first if needed
+    pub fn entry(&mut self, point: Point) -> &mut T {
+        if !self.cells.contains_key(&point) {
+            self.insert(point, self.default.clone());
+        }
+        self.cells.get_mut(&point).unwrap()
+    }
+
+    /// Number of stored cells
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Every stored cell, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    /// Every point with a stored cell, in no particular order
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        self.cells.keys().copied()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+}
+
+impl<T: Clone> Grid for SparseGrid<T> {
+    type Cell = T;
+    type Mapped<U: Clone> = SparseGrid<U>;
+
+    /// Always has a value, since unset points read as the default
+    fn get(&self, point: Point) -> Option<&T> {
+        Some(&self[point])
+    }
+
+    fn set(&mut self, point: Point, value: T) {
+        self.insert(point, value);
+    }
+
+    /// Covers every stored cell. An empty grid has an empty rectangle with no points.
+    fn bounds(&self) -> Rect {
+        self.bounds.unwrap_or(Rect { min: Point::new(0, 0), max: Point::new(-1, -1) })
+    }
+
+    fn map<U: Clone>(&self, mut f: impl FnMut(&T) -> U) -> SparseGrid<U> {
+        SparseGrid {
+            default: f(&self.default),
+            cells: self.cells.iter().map(|(&point, value)| (point, f(value))).collect(),
+            bounds: self.bounds,
+        }
+    }
+}
+
+impl<T: Clone> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.cells.get(&point).unwrap_or(&self.default)
+    }
+}
+
+impl<T: Clone> FromIterator<(Point, T)> for SparseGrid<T>
+    where T: Default
+{
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new(T::default());
+        for (point, value) in iter {
+            grid.insert(point, value);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(0, grid.bounds().points().count());
+        grid.set(Point::new(-2, 1), '#');
+        grid.set(Point::new(3, -1), '#');
+        *grid.entry(Point::new(0, 4)) = '@';
+        assert_eq!(Rect::from_corners(Point::new(-2, -1), Point::new(3, 4)), grid.bounds());
+        assert_eq!(Some(&'.'), grid.get(Point::new(100, 100)));
+
+        grid.remove(Point::new(0, 4));
+        assert_eq!(Rect::from_corners(Point::new(-2, -1), Point::new(3, 1)), grid.bounds());
+        assert_eq!('.', grid[Point::new(0, 4)]);
+        assert_eq!(2, grid.len());
+    }
+
+    #[test]
+    fn test_neighbors_and_render() {
+        let grid = [(Point::new(-1, -1), 1), (Point::new(1, 0), 2)].into_iter().collect::<SparseGrid<u32>>();
+        // neighbors outside the bounds still read as the default
+        assert_eq!(8, grid.neighbors8(Point::new(-1, -1)).count());
+        assert_eq!(2, grid.neighbors4(Point::new(0, 0)).map(|(_, _, &v)| v).sum::<u32>());
+        let rendered = grid.render(|&v| char::from_digit(v, 10).unwrap()).to_string();
+        assert_eq!("100\n002", rendered);
+    }
+}
@@ -0,0 +1,223 @@
+use super::point::Point;
+use super::rect::Rect;
+use super::vec2d::Vec2d;
+
+impl<T: Clone> Vec2d<T> {
+
+    /// Builds a `width` x `height` grid where each cell is copied from the point `source` picks.
+    /// Like every transform, the result keeps this grid's edge policy.
+    fn remap(&self, width: i32, height: i32, source: impl Fn(Point) -> Point) -> Vec2d<T> {
+        let bounds = Rect { min: Point::new(0, 0), max: Point::new(width - 1, height - 1) };
+        let grid = bounds.points().map(|point| self[source(point)].clone()).collect();
+        Vec2d::new(grid, width).with_edges(self.edges().clone())
+    }
+
+    /// Swaps rows and columns, mirroring the grid across its top-left to bottom-right diagonal
+    #[must_use]
+    pub fn transpose(&self) -> Vec2d<T> {
+        self.remap(self.height(), self.width(), |p| Point::new(p.y, p.x))
+    }
+
+    /// Turns the grid 90° clockwise
+    #[must_use]
+    pub fn rotate_cw(&self) -> Vec2d<T> {
+        let height = self.height();
+        self.remap(height, self.width(), |p| Point::new(p.y, height - 1 - p.x))
+    }
+
+    /// Turns the grid 90° counter-clockwise, so the rightmost column becomes the top row
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Vec2d<T> {
+        let width = self.width();
+        self.remap(self.height(), width, |p| Point::new(width - 1 - p.y, p.x))
+    }
+
+    /// Turns the grid 180°
+    #[must_use]
+    pub fn rotate_180(&self) -> Vec2d<T> {
+        let mut grid = self.clone();
+        grid.grid.reverse();
+        grid
+    }
+
+    /// Mirrors the grid left to right
+    #[must_use]
+    pub fn flip_h(&self) -> Vec2d<T> {
+        let mut grid = self.clone();
+        grid.flip_h_in_place();
+        grid
+    }
+
+    /// Mirrors the grid top to bottom
+    #[must_use]
+    pub fn flip_v(&self) -> Vec2d<T> {
+        let mut grid = self.clone();
+        grid.flip_v_in_place();
+        grid
+    }
+
+    /// Copies out the part of the grid covered by `rect`
+    ///
+    /// # Panics
+    /// If `rect` is not entirely inside the grid
+    #[must_use]
+    pub fn crop(&self, rect: &Rect) -> Vec2d<T> {
+        assert!(self.bounds().contains_rect(rect), "Crop {rect:?} is outside the grid");
+        self.remap(rect.width(), rect.height(), |p| rect.to_global(p))
+    }
+
+    /// Adds a border `n` cells wide of `fill` around every side
+    #[must_use]
+    pub fn pad(&self, n: i32, fill: T) -> Vec2d<T> {
+        let mut grid = Vec2d::filled(self.width() + 2 * n, self.height() + 2 * n, fill).with_edges(self.edges().clone());
+        for (point, cell) in self.enumerate() {
+            grid[point + n] = cell.clone();
+        }
+        grid
+    }
+
+    /// Inserts a row of `fill` so that it becomes row `y`. Use the height to append a row.
+    ///
+    /// # Panics
+    /// If `y` is not between 0 and the grid's height
+    #[must_use]
+    pub fn insert_row(&self, y: i32, fill: T) -> Vec2d<T> {
+        assert!(y >= 0 && y <= self.height(), "Invalid row {y}");
+        let mut grid = self.grid.clone();
+        let idx = (y * self.line_len) as usize;
+        grid.splice(idx .. idx, vec![fill; self.line_len as usize]);
+        Vec2d::new(grid, self.line_len).with_edges(self.edges().clone())
+    }
+
+    /// Inserts a column of `fill` so that it becomes column `x`. Use the width to append a column.
+    ///
+    /// # Panics
+    /// If `x` is not between 0 and the grid's width
+    #[must_use]
+    pub fn insert_col(&self, x: i32, fill: T) -> Vec2d<T> {
+        assert!(x >= 0 && x <= self.width(), "Invalid column {x}");
+        let grid = self.rows()
+            .flat_map(|row| {
+                let (left, right) = row.split_at(x as usize);
+                left.iter().chain(std::iter::once(&fill)).chain(right).cloned()
+            })
+            .collect();
+        Vec2d::new(grid, self.line_len + 1).with_edges(self.edges().clone())
+    }
+
+    pub fn flip_h_in_place(&mut self) {
+        self.grid.chunks_mut(self.line_len as usize).for_each(<[T]>::reverse);
+    }
+
+    pub fn flip_v_in_place(&mut self) {
+        let (width, height) = (self.line_len as usize, self.height() as usize);
+        for y in 0 .. height / 2 {
+            let (top, bottom) = self.grid.split_at_mut((height - 1 - y) * width);
+            top[y * width .. (y + 1) * width].swap_with_slice(&mut bottom[.. width]);
+        }
+    }
+
+    pub fn rotate_180_in_place(&mut self) {
+        self.grid.reverse();
+    }
+
+    /// Transposes a square grid without allocating
+    ///
+    /// # Panics
+    /// If the grid is not square
+    pub fn transpose_in_place(&mut self) {
+        assert_eq!(self.width(), self.height(), "Only square grids can be transposed in place");
+        let size = self.line_len as usize;
+        for y in 0 .. size {
+            for x in y + 1 .. size {
+                self.grid.swap(y * size + x, x * size + y);
+            }
+        }
+    }
+
+    /// Turns a square grid 90° clockwise without allocating
+    ///
+    /// # Panics
+    /// If the grid is not square
+    pub fn rotate_cw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_h_in_place();
+    }
+
+    /// Turns a square grid 90° counter-clockwise without allocating
+    ///
+    /// # Panics
+    /// If the grid is not square
+    pub fn rotate_ccw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_v_in_place();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::vec2d::EdgePolicy;
+
+    fn grid(text: &str) -> Vec2d<char> {
+        Vec2d::parse(text, |c| c).unwrap()
+    }
+
+    #[test]
+    fn test_rotations() {
+        let original = grid("abc\ndef");
+        assert_eq!(grid("ad\nbe\ncf"), original.transpose());
+        assert_eq!(grid("da\neb\nfc"), original.rotate_cw());
+        assert_eq!(grid("cf\nbe\nad"), original.rotate_ccw());
+        assert_eq!(grid("fed\ncba"), original.rotate_180());
+        assert_eq!(original.rotate_180(), original.rotate_cw().rotate_cw());
+        assert_eq!(original, original.rotate_cw().rotate_ccw());
+        assert_eq!(grid("cba\nfed"), original.flip_h());
+        assert_eq!(grid("def\nabc"), original.flip_v());
+    }
+
+    #[test]
+    fn test_keeps_edge_policy() {
+        let wrapping = grid("abc\ndef").with_edges(EdgePolicy::Wrap);
+        assert_eq!(wrapping, wrapping.rotate_cw().rotate_ccw());
+        assert_eq!(wrapping, wrapping.transpose().transpose());
+        let with_default = grid("ab\ncd").with_edges(EdgePolicy::Default('#'));
+        for transformed in [
+            with_default.crop(&Rect::from_corners(Point::new(0, 0), Point::new(0, 1))),
+            with_default.pad(1, '.'),
+            with_default.insert_row(0, '.'),
+            with_default.insert_col(0, '.'),
+        ] {
+            assert_eq!(&EdgePolicy::Default('#'), transformed.edges());
+        }
+    }
+
+    #[test]
+    fn test_in_place() {
+        let square = grid("abc\ndef\nghi");
+        for (transform, in_place) in [
+            (Vec2d::transpose as fn(&Vec2d<char>) -> Vec2d<char>, Vec2d::transpose_in_place as fn(&mut Vec2d<char>)),
+            (Vec2d::rotate_cw, Vec2d::rotate_cw_in_place),
+            (Vec2d::rotate_ccw, Vec2d::rotate_ccw_in_place),
+            (Vec2d::rotate_180, Vec2d::rotate_180_in_place),
+            (Vec2d::flip_h, Vec2d::flip_h_in_place),
+            (Vec2d::flip_v, Vec2d::flip_v_in_place),
+        ] {
+            let mut grid = square.clone();
+            in_place(&mut grid);
+            assert_eq!(transform(&square), grid);
+        }
+    }
+
+    #[test]
+    fn test_crop_pad_insert() {
+        let original = grid("abcd\nefgh\nijkl");
+        let rect = Rect::from_corners(Point::new(1, 1), Point::new(2, 2));
+        assert_eq!(grid("fg\njk"), original.crop(&rect));
+        assert_eq!(grid("....\n.ab.\n.cd.\n...."), grid("ab\ncd").pad(1, '.'));
+        assert_eq!(grid("ab\n..\ncd"), grid("ab\ncd").insert_row(1, '.'));
+        assert_eq!(grid("ab\ncd\n.."), grid("ab\ncd").insert_row(2, '.'));
+        assert_eq!(grid(".ab\n.cd"), grid("ab\ncd").insert_col(0, '.'));
+        assert_eq!(grid("a.b\nc.d"), grid("ab\ncd").insert_col(1, '.'));
+    }
+}
@@ -1,5 +1,9 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
 use std::ops::{Index, IndexMut};
-use super::point::Point;
+use super::point::{Coordinate, Point};
+use super::rect::Rect;
 
 #[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
 pub enum Directions {
@@ -13,21 +17,406 @@ pub enum Directions {
     UpLeft,
 }
 
-#[derive(Clone)]
+impl Directions {
+    /// Up, right, down and left, clockwise from up
+    pub const CARDINAL: [Directions; 4] = [Directions::Up, Directions::Right, Directions::Down, Directions::Left];
+    /// All eight directions, clockwise from up
+    pub const ALL: [Directions; 8] = [Directions::Up, Directions::UpRight, Directions::Right, Directions::DownRight,
+        Directions::Down, Directions::DownLeft, Directions::Left, Directions::UpLeft];
+    /// The four diagonals, clockwise from up and right
+    pub const DIAGONAL: [Directions; 4] = [Directions::UpRight, Directions::DownRight, Directions::DownLeft, Directions::UpLeft];
+
+    /// The direction `eighth_turns` steps of 45° clockwise from this one.
+    /// Variants are declared in clockwise order, so this is arithmetic on the index into [`Self::ALL`].
+    fn rotate(self, eighth_turns: usize) -> Self {
+        Self::ALL[(self as usize + eighth_turns) % 8]
+    }
+
+    /// Turns 90° clockwise
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns 90° counter-clockwise
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Turns 45° clockwise
+    #[must_use]
+    pub fn turn_right_45(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Turns 45° counter-clockwise
+    #[must_use]
+    pub fn turn_left_45(self) -> Self {
+        self.rotate(7)
+    }
+
+    /// Turns 90° in the direction of `turn`
+    #[must_use]
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// The change in position from one step in this direction. Up is negative y.
+    #[must_use]
+    pub fn to_point(self) -> Point {
+        self.into()
+    }
+
+    /// True for up, right, down and left
+    #[must_use]
+    pub fn is_cardinal(self) -> bool {
+        (self as usize).is_multiple_of(2)
+    }
+}
+
+/// A character that doesn't name a direction or turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseDirectionError(pub char);
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid direction '{}'", self.0)
+    }
+}
+
+impl Error for ParseDirectionError {}
+
+/// Reads arrows (`^>v<`), `UDLR` or compass points (`NESW`), in either case
+impl TryFrom<char> for Directions {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_ascii_uppercase() {
+            '^' | 'U' | 'N' => Ok(Directions::Up),
+            '>' | 'R' | 'E' => Ok(Directions::Right),
+            'V' | 'D' | 'S' => Ok(Directions::Down),
+            '<' | 'L' | 'W' => Ok(Directions::Left),
+            _ => Err(ParseDirectionError(c)),
+        }
+    }
+}
+
+/// A turn relative to the current facing
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+/// Reads `L` or `R`, in either case
+impl TryFrom<char> for Turn {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_ascii_uppercase() {
+            'L' => Ok(Turn::Left),
+            'R' => Ok(Turn::Right),
+            _ => Err(ParseDirectionError(c)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError<E = Infallible> {
+    /// There were no cells to build a grid from
+    Empty,
+    /// A row (1 indexed) is a different length than the first row.
+    /// If the first row is blank, it is reported as the bad row, and the length of the next row is expected.
+    RaggedRow { line: usize, expected: usize, found: usize },
+    /// A character could not be converted to a cell. `line` and `column` are 1 indexed.
+    InvalidCell { line: usize, column: usize, error: E },
+    /// The rows are too long to index with an `i32`
+    TooWide(usize),
+}
+
+impl<E: Display> Display for GridError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "grid is empty"),
+            Self::RaggedRow { line, expected, found } =>
+                write!(f, "line {line} has {found} cells, expected {expected}"),
+            Self::InvalidCell { line, column, error } =>
+                write!(f, "invalid cell at line {line}, column {column}: {error}"),
+            Self::TooWide(len) => write!(f, "lines of {len} cells are too long"),
+        }
+    }
+}
+
+impl<E: Debug + Display> Error for GridError<E> {}
+
+fn check_line_len<E>(line_len: &mut Option<usize>, line: usize, found: usize) -> Result<(), GridError<E>> {
+    match *line_len {
+        None => *line_len = Some(found),
+        // a blank first line is the odd one out, rather than every line after it
+        Some(0) if found > 0 => return Err(GridError::RaggedRow { line: 1, expected: found, found: 0 }),
+        Some(expected) if expected != found => return Err(GridError::RaggedRow { line, expected, found }),
+        _ => (),
+    }
+    Ok(())
+}
+
+impl<T: Coordinate> From<Directions> for Point<T> {
+    /// The unit vector for one step in this direction. Up is negative y.
+    fn from(direction: Directions) -> Self {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match direction {
+            Directions::Up => Point::new(zero, -one),
+            Directions::UpRight => Point::new(one, -one),
+            Directions::Right => Point::new(one, zero),
+            Directions::DownRight => Point::new(one, one),
+            Directions::Down => Point::new(zero, one),
+            Directions::DownLeft => Point::new(-one, one),
+            Directions::Left => Point::new(-one, zero),
+            Directions::UpLeft => Point::new(-one, -one),
+        }
+    }
+}
+
+/// How a [`Vec2d`] treats points outside of the grid in [`Vec2d::get`] and [`Vec2d::step`]
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub enum EdgePolicy<T> {
+    /// Points outside the grid have no value
+    Bounded,
+    /// The grid repeats forever in every direction, like a torus
+    Wrap,
+    /// Every point outside the grid reads as this value
+    Default(T),
+    /// Points outside the grid read as the closest cell on the edge
+    Clamp,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
 pub struct Vec2d<T> 
     where T: Clone
 {
     pub grid: Vec<T>,
     pub line_len: i32,
+    edges: EdgePolicy<T>,
 }
 
 impl<T> Vec2d<T> 
     where T: Clone
 {
+    /// # Panics
+    /// If `line_len` is not positive or the grid is not made of complete lines
+    #[must_use]
+    pub fn new(grid: Vec<T>, line_len: i32) -> Self {
+        assert!(line_len > 0, "Invalid line_len {line_len}");
+        assert!(grid.len().is_multiple_of(line_len as usize), "Grid of {} cells is not made of lines of {line_len}", grid.len());
+        Self { grid, line_len, edges: EdgePolicy::Bounded }
+    }
+
+    /// Sets how points outside the grid are handled. Grids start out [`EdgePolicy::Bounded`].
+    #[must_use]
+    pub fn with_edges(mut self, edges: EdgePolicy<T>) -> Self {
+        self.edges = edges;
+        self
+    }
+
+    pub fn set_edges(&mut self, edges: EdgePolicy<T>) {
+        self.edges = edges;
+    }
+
+    #[must_use]
+    pub fn edges(&self) -> &EdgePolicy<T> {
+        &self.edges
+    }
+
+    /// The point inside the grid that holds the value for `point` under the edge policy.
+    /// Returns `None` for points outside a bounded grid, or outside a grid with a default value.
+    #[must_use]
+    pub fn resolve(&self, point: Point) -> Option<Point> {
+        if self.in_bounds(point) {
+            return Some(point);
+        }
+        match self.edges {
+            EdgePolicy::Bounded | EdgePolicy::Default(_) => None,
+            EdgePolicy::Wrap => Some(Point::new(point.x.rem_euclid(self.width()), point.y.rem_euclid(self.height()))),
+            EdgePolicy::Clamp => Some(Point::new(point.x.clamp(0, self.width() - 1), point.y.clamp(0, self.height() - 1))),
+        }
+    }
+
+    /// The value at `point`, following the edge policy for points outside the grid
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        match (self.resolve(point), &self.edges) {
+            (Some(point), _) => Some(&self[point]),
+            (None, EdgePolicy::Default(value)) => Some(value),
+            (None, _) => None,
+        }
+    }
+
+    /// A mutable reference to the value at `point`, following the edge policy.
+    /// The default value for points outside the grid cannot be changed, so those return `None`.
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.resolve(point).map(|point| &mut self[point])
+    }
+
+    /// Takes one step in `direction`, following the edge policy.
+    ///
+    /// Steps off a bounded grid return `None`, steps off a wrapping or clamped grid return the
+    /// point [`Self::resolve`] maps them to, and steps off a grid with a default value
+    /// return the point outside the grid.
+    #[must_use]
+    pub fn step(&self, point: Point, direction: Directions) -> Option<Point> {
+        let next = self.next_unbounded(point, direction);
+        match self.edges {
+            EdgePolicy::Default(_) => Some(next),
+            _ => self.resolve(next),
+        }
+    }
+
+    /// # Panics
+    /// If `width` or `height` is not positive, or there are more cells than fit in an `i32`
+    #[must_use]
+    pub fn filled(width: i32, height: i32, value: T) -> Self {
+        assert!(width > 0, "Invalid width {width}");
+        assert!(height > 0, "Invalid height {height}");
+        let len = width.checked_mul(height).unwrap_or_else(|| panic!("Grid of {width}x{height} is too large"));
+        Self::new(vec![value; len as usize], width)
+    }
+
+    /// Builds a grid of the same size with `f` applied to every cell, and to the edge policy's default value
+    pub fn map<U: Clone>(&self, mut f: impl FnMut(&T) -> U) -> Vec2d<U> {
+        let edges = match &self.edges {
+            EdgePolicy::Bounded => EdgePolicy::Bounded,
+            EdgePolicy::Wrap => EdgePolicy::Wrap,
+            EdgePolicy::Default(value) => EdgePolicy::Default(f(value)),
+            EdgePolicy::Clamp => EdgePolicy::Clamp,
+        };
+        Vec2d::new(self.grid.iter().map(f).collect(), self.line_len).with_edges(edges)
+    }
+
+    /// Builds a grid from text, one line per row, converting each character with `cell`.
+    /// Trailing blank lines are ignored and `\r\n` line endings are accepted.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        Self::try_parse(input, |c| Ok::<T, Infallible>(cell(c)))
+    }
+
+    /// Same as [`Self::parse`], for when a character might not convert to a valid cell
+    pub fn try_parse<E>(input: &str, mut cell: impl FnMut(char) -> Result<T, E>) -> Result<Self, GridError<E>> {
+        let lines = input.trim_end_matches(['\r', '\n']).lines();
+        let mut grid = vec![];
+        let mut line_len = None;
+        for (i, line) in lines.enumerate() {
+            let mut found = 0;
+            for (column, c) in line.chars().enumerate() {
+                let value = cell(c).map_err(|error| GridError::InvalidCell { line: i + 1, column: column + 1, error })?;
+                grid.push(value);
+                found += 1;
+            }
+            check_line_len(&mut line_len, i + 1, found)?;
+        }
+        Self::from_parts(grid, line_len)
+    }
+
+    /// Builds a grid from rows of cells. Every row must be the same length.
+    pub fn from_rows<R: IntoIterator<Item = T>>(rows: impl IntoIterator<Item = R>) -> Result<Self, GridError> {
+        let mut grid = vec![];
+        let mut line_len = None;
+        for (i, row) in rows.into_iter().enumerate() {
+            let before = grid.len();
+            grid.extend(row);
+            check_line_len(&mut line_len, i + 1, grid.len() - before)?;
+        }
+        Self::from_parts(grid, line_len)
+    }
+
+    fn from_parts<E>(grid: Vec<T>, line_len: Option<usize>) -> Result<Self, GridError<E>> {
+        match line_len {
+            None | Some(0) => Err(GridError::Empty),
+            Some(line_len) => {
+                let line_len = line_len.try_into().map_err(|_| GridError::TooWide(line_len))?;
+                Ok(Self::new(grid, line_len))
+            }
+        }
+    }
+
     #[must_use]
     pub fn in_bounds(&self, point: Point) -> bool {
-        let max_y = self.grid.len() as i32 / self.line_len;
-        point.x >= 0 && point.y >= 0 && point.x < self.line_len && point.y < max_y 
+        point.x >= 0 && point.y >= 0 && point.x < self.line_len
+            && (point.y as usize) * (self.line_len as usize) < self.grid.len()
+    }
+
+    #[must_use]
+    pub fn width(&self) -> i32 {
+        self.line_len
+    }
+
+    #[must_use]
+    pub fn height(&self) -> i32 {
+        self.grid.len() as i32 / self.line_len
+    }
+
+    /// The rectangle covering every point in the grid
+    #[must_use]
+    pub fn bounds(&self) -> Rect {
+        Rect { min: Point::new(0, 0), max: Point::new(self.width() - 1, self.height() - 1) }
+    }
+
+    /// Each row as a slice, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.grid.chunks(self.line_len as usize)
+    }
+
+    /// Each column as an iterator running top to bottom, left to right
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0 .. self.line_len).map(|x| self.col(x))
+    }
+
+    /// # Panics
+    /// If `y` is outside the grid
+    #[must_use]
+    pub fn row(&self, y: i32) -> &[T] {
+        assert!(y >= 0 && y < self.height(), "Invalid row {y}");
+        let start = (y * self.line_len) as usize;
+        &self.grid[start .. start + self.line_len as usize]
+    }
+
+    /// # Panics
+    /// If `x` is outside the grid
+    pub fn col(&self, x: i32) -> impl Iterator<Item = &T> {
+        assert!(x >= 0 && x < self.line_len, "Invalid column {x}");
+        self.grid.iter().skip(x as usize).step_by(self.line_len as usize)
+    }
+
+    /// Every cell with its point, in reading order
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.grid.iter().enumerate().map(|(idx, cell)| (self.idx_to_point(idx), cell))
+    }
+
+    /// Every point whose cell matches the predicate, in reading order
+    pub fn positions(&self, predicate: impl Fn(&T) -> bool) -> impl Iterator<Item = Point> {
+        self.enumerate()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// Every `width` x `height` sub-grid, sliding one cell at a time in reading order.
+    /// Yields nothing if the window is bigger than the grid.
+    pub fn windows(&self, width: i32, height: i32) -> impl Iterator<Item = Window<'_, T>> {
+        // an empty Rect has no points, so oversized windows produce nothing
+        let last = if width > 0 && height > 0 {
+            Point::new(self.width() - width, self.height() - height)
+        } else {
+            Point::new(-1, -1)
+        };
+        Rect { min: Point::new(0, 0), max: last }.points()
+            .map(move |origin| Window { grid: self, origin, width, height })
     }
 
     /// # Panics
@@ -56,16 +445,7 @@ impl<T> Vec2d<T>
     /// up the grid value at this point could panic. See [`Self::next_point`].
     #[must_use]
     pub fn next_unbounded(&self, point: Point, direction: Directions) -> Point {
-        match direction {
-            Directions::Down => Point::new(point.x, point.y + 1),
-            Directions::DownLeft => Point::new(point.x - 1, point.y + 1),
-            Directions::DownRight => Point::new(point.x + 1, point.y + 1),
-            Directions::Up => Point::new(point.x, point.y - 1),
-            Directions::UpLeft => Point::new(point.x - 1, point.y - 1),
-            Directions::UpRight => Point::new(point.x + 1, point.y - 1),
-            Directions::Left => Point::new(point.x - 1, point.y),
-            Directions::Right => Point::new(point.x + 1, point.y),
-        }
+        point + direction.to_point()
     }
 
     /// Finds the next point in the grid in the direction specified.
@@ -79,6 +459,22 @@ impl<T> Vec2d<T>
             None
         }
     }
+
+    /// The cells next to `point` in each of the given directions that are inside the grid
+    pub fn neighbors<'a>(&'a self, point: Point, directions: &'a [Directions]) -> impl Iterator<Item = (Directions, Point, &'a T)> {
+        directions.iter()
+            .filter_map(move |&direction| self.next_point(point, direction).map(|next| (direction, next, &self[next])))
+    }
+
+    /// The up to 4 cells directly above, below, left and right of `point`
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Directions, Point, &T)> {
+        self.neighbors(point, &Directions::CARDINAL)
+    }
+
+    /// The up to 8 cells surrounding `point`, including diagonals
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Directions, Point, &T)> {
+        self.neighbors(point, &Directions::ALL)
+    }
 }
 
 impl <T> Vec2d<T> 
@@ -92,6 +488,64 @@ impl <T> Vec2d<T>
     }
 }
 
+/// A borrowed rectangular section of a [`Vec2d`]. Points are relative to the window's top left.
+#[derive(Copy, Clone, Debug)]
+pub struct Window<'a, T: Clone> {
+    grid: &'a Vec2d<T>,
+    origin: Point,
+    width: i32,
+    height: i32,
+}
+
+impl<'a, T: Clone> Window<'a, T> {
+
+    /// Where the window's top left corner is in the full grid
+    #[must_use]
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    #[must_use]
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&'a T> {
+        let in_window = point.x >= 0 && point.y >= 0 && point.x < self.width && point.y < self.height;
+        in_window.then(|| &self.grid[self.origin + point])
+    }
+
+    /// Each row of the window as a slice of the full grid
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + use<'a, T> {
+        let Self { grid, origin, width, height } = *self;
+        (origin.y .. origin.y + height).map(move |y| {
+            let start = grid.point_to_idx(Point::new(origin.x, y));
+            &grid.grid[start .. start + width as usize]
+        })
+    }
+
+    /// Copies the window out into its own grid
+    #[must_use]
+    pub fn to_vec2d(&self) -> Vec2d<T> {
+        let grid = self.rows().flat_map(|row| row.iter().cloned()).collect();
+        Vec2d::new(grid, self.width)
+    }
+}
+
+impl<T: Clone> Index<Point> for Window<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Point) -> &Self::Output {
+        self.get(index).expect("Invalid Window index")
+    }
+}
+
 impl <T: Clone> Index<Point> for Vec2d<T>{
     type Output = T;
 
@@ -107,4 +561,144 @@ impl <T: Clone> IndexMut<Point> for Vec2d<T> {
         let idx = self.point_to_idx(index);
         self.grid.get_mut(idx).expect("Invalid Index")
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid = Vec2d::parse("#.#\r\n..#\r\n\r\n", |c| c == '#').unwrap();
+        assert_eq!(3, grid.line_len);
+        assert_eq!(vec![true, false, true, false, false, true], grid.grid);
+
+        let ragged = Vec2d::parse("...\n..\n...", |c| c);
+        assert_eq!(Err(GridError::RaggedRow { line: 2, expected: 3, found: 2 }), ragged);
+        assert_eq!("line 2 has 2 cells, expected 3", ragged.unwrap_err().to_string());
+        assert_eq!(Err(GridError::Empty), Vec2d::parse("\n", |c| c));
+        let leading_blank = Vec2d::parse("\nabc\ndef", |c| c);
+        assert_eq!(Err(GridError::RaggedRow { line: 1, expected: 3, found: 0 }), leading_blank);
+        assert_eq!("line 1 has 0 cells, expected 3", leading_blank.unwrap_err().to_string());
+
+        let digits = Vec2d::try_parse("123\n4x6", |c| c.to_digit(10).ok_or(c));
+        assert_eq!(Err(GridError::InvalidCell { line: 2, column: 2, error: 'x' }), digits);
+    }
+
+    #[test]
+    fn test_from_rows_and_filled() {
+        let grid = Vec2d::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap();
+        assert_eq!(Point::new(1, 2), grid.bounds().max);
+        assert_eq!(6, grid[Point::new(1, 2)]);
+        assert_eq!(Err(GridError::RaggedRow { line: 3, expected: 2, found: 1 }), Vec2d::from_rows(vec![vec![1, 2], vec![3, 4], vec![5]]));
+
+        let filled = Vec2d::filled(4, 2, '.');
+        assert_eq!(8, filled.grid.len());
+        assert_eq!(4, filled.line_len);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid width -4")]
+    fn test_filled_negative_width() {
+        let _ = Vec2d::filled(-4, -2, '.');
+    }
+
+    #[test]
+    fn test_rows_and_cols() {
+        let grid = Vec2d::parse("abc\ndef", |c| c).unwrap();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(vec![&['a', 'b', 'c'][..], &['d', 'e', 'f'][..]], grid.rows().collect::<Vec<_>>());
+        assert_eq!(['d', 'e', 'f'], grid.row(1));
+        assert_eq!(vec![&'b', &'e'], grid.col(1).collect::<Vec<_>>());
+        let transposed = grid.cols().map(|col| col.collect::<String>()).collect::<Vec<_>>();
+        assert_eq!(vec!["ad", "be", "cf"], transposed);
+        assert!(grid.in_bounds(Point::new(2, 1)));
+        assert!(!grid.in_bounds(Point::new(2, 2)) && !grid.in_bounds(Point::new(3, 0)));
+    }
+
+    #[test]
+    fn test_enumerate_and_positions() {
+        let grid = Vec2d::parse("#.#\n.##", |c| c == '#').unwrap();
+        assert_eq!(Some((Point::new(1, 0), &false)), grid.enumerate().nth(1));
+        let walls = grid.positions(|&wall| wall).collect::<Vec<_>>();
+        assert_eq!(vec![Point::new(0, 0), Point::new(2, 0), Point::new(1, 1), Point::new(2, 1)], walls);
+    }
+
+    #[test]
+    fn test_windows() {
+        let grid = Vec2d::parse("abcd\nefgh\nijkl", |c| c).unwrap();
+        let windows = grid.windows(3, 2).collect::<Vec<_>>();
+        assert_eq!(4, windows.len());
+        assert_eq!(Point::new(1, 1), windows[3].origin());
+        assert_eq!('l', windows[3][Point::new(2, 1)]);
+        assert_eq!(None, windows[3].get(Point::new(3, 0)));
+        assert_eq!(Vec2d::parse("fgh\njkl", |c| c).unwrap(), windows[3].to_vec2d());
+        assert_eq!(0, grid.windows(5, 1).count());
+    }
+
+    #[test]
+    fn test_edge_policies() {
+        let grid = Vec2d::parse("abc\ndef", |c| c).unwrap();
+        let outside = Point::new(-1, 2);
+        assert_eq!(None, grid.get(outside));
+        assert_eq!(None, grid.step(Point::new(0, 0), Directions::Left));
+
+        let mut wrapped = grid.clone().with_edges(EdgePolicy::Wrap);
+        assert_eq!(Some(&'c'), wrapped.get(outside));
+        assert_eq!(Some(&'f'), wrapped.get(Point::new(-4, -3)));
+        assert_eq!(Some(Point::new(2, 0)), wrapped.step(Point::new(0, 0), Directions::Left));
+        *wrapped.get_mut(outside).unwrap() = 'z';
+        assert_eq!('z', wrapped[Point::new(2, 0)]);
+
+        let clamped = grid.clone().with_edges(EdgePolicy::Clamp);
+        assert_eq!(Some(&'d'), clamped.get(outside));
+        assert_eq!(Some(Point::new(0, 1)), clamped.step(Point::new(0, 1), Directions::DownLeft));
+
+        let mut padded = grid.with_edges(EdgePolicy::Default('.'));
+        assert_eq!(Some(&'.'), padded.get(outside));
+        assert_eq!(None, padded.get_mut(outside));
+        assert_eq!(Some(Point::new(0, -1)), padded.step(Point::new(0, 0), Directions::Up));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Vec2d::parse("abc\ndef\nghi", |c| c).unwrap();
+        let around_center = grid.neighbors8(Point::new(1, 1)).map(|(_, _, &c)| c).collect::<String>();
+        assert_eq!("bcfihgda", around_center);
+        let corner = grid.neighbors4(Point::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(vec![(Directions::Right, Point::new(1, 0), &'b'), (Directions::Down, Point::new(0, 1), &'d')], corner);
+        assert_eq!(3, grid.neighbors8(Point::new(2, 2)).count());
+        let diagonals = grid.neighbors(Point::new(0, 1), &[Directions::UpRight, Directions::UpLeft]).collect::<Vec<_>>();
+        assert_eq!(vec![(Directions::UpRight, Point::new(1, 0), &'b')], diagonals);
+    }
+
+    #[test]
+    fn test_direction_rotation() {
+        let clockwise = Directions::ALL;
+        for (i, &direction) in clockwise.iter().enumerate() {
+            let vector: Point = direction.into();
+            assert_eq!(Point::from(clockwise[(i + 2) % 8]), vector.rotate_right());
+            assert_eq!(Point::from(clockwise[(i + 6) % 8]), vector.rotate_left());
+            assert_eq!(Point::from(clockwise[(i + 4) % 8]), vector.rotate_180());
+            assert_eq!(vector.rotate_right(), direction.turn_right().to_point());
+            assert_eq!(vector.rotate_left(), direction.turn_left().to_point());
+            assert_eq!(-vector, direction.opposite().to_point());
+            assert_eq!(direction, direction.turn_right_45().turn_left_45());
+            assert_eq!(direction.turn_right(), direction.turn_right_45().turn_right_45());
+            assert_eq!(direction.turn_left(), direction.turn(Turn::Left));
+            assert_eq!(vector.x == 0 || vector.y == 0, direction.is_cardinal());
+        }
+        assert!(Directions::DIAGONAL.iter().all(|d| !d.is_cardinal()));
+    }
+
+    #[test]
+    fn test_parse_directions() {
+        for (text, expected) in [("^>v<", Directions::CARDINAL), ("URDL", Directions::CARDINAL), ("nesw", Directions::CARDINAL)] {
+            let parsed = text.chars().map(Directions::try_from).collect::<Result<Vec<_>, _>>();
+            assert_eq!(Ok(expected.to_vec()), parsed);
+        }
+        assert_eq!(Err(ParseDirectionError('x')), Directions::try_from('x'));
+        assert_eq!(Ok(Turn::Right), Turn::try_from('R'));
+        assert_eq!(Err(ParseDirectionError('U')), Turn::try_from('U'));
+    }
 }
\ No newline at end of file
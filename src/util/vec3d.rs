@@ -0,0 +1,283 @@
+use std::collections::VecDeque;
+use std::ops::{Index, IndexMut};
+use super::point::Point;
+use super::point3d::Point3d;
+use super::rect::{Cuboid, Rect};
+use super::render::Renderer;
+use super::vec2d::Vec2d;
+
+/// One of the three axes of 3d space
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+
+    /// Turns a point on a slice across this axis at `at` back into the 3d point it came from.
+    /// Slices keep the two remaining axes in x, y, z order, so slicing across z keeps `(x, y)`,
+    /// across y keeps `(x, z)` and across x keeps `(y, z)`.
+    fn unslice(self, point: Point, at: i32) -> Point3d {
+        match self {
+            Axis::X => Point3d::new(at, point.x, point.y),
+            Axis::Y => Point3d::new(point.x, at, point.y),
+            Axis::Z => Point3d::new(point.x, point.y, at),
+        }
+    }
+
+    fn slice_point(self, point: Point3d) -> Point {
+        match self {
+            Axis::X => Point::new(point.y, point.z),
+            Axis::Y => Point::new(point.x, point.z),
+            Axis::Z => Point::new(point.x, point.y),
+        }
+    }
+}
+
+/// A dense 3d grid covering every point of a [`Cuboid`], which doesn't have to start at the origin.
+/// Cells are stored in reading order for [`Point3d`]: by z, then y, then x.
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub struct Vec3d<T> {
+    cells: Vec<T>,
+    bounds: Cuboid,
+}
+
+impl<T: Clone> Vec3d<T> {
+
+    /// # Panics
+    /// If `bounds` is inverted along any axis
+    #[must_use]
+    pub fn filled(bounds: Cuboid, value: T) -> Self {
+        assert!(bounds.min.component_min(&bounds.max) == bounds.min, "Invalid bounds {bounds:?}");
+        Self { cells: vec![value; bounds.volume() as usize], bounds }
+    }
+
+    #[must_use]
+    pub fn bounds(&self) -> Cuboid {
+        self.bounds
+    }
+
+    #[must_use]
+    pub fn width(&self) -> i32 {
+        self.bounds.width()
+    }
+
+    #[must_use]
+    pub fn height(&self) -> i32 {
+        self.bounds.height()
+    }
+
+    #[must_use]
+    pub fn depth(&self) -> i32 {
+        self.bounds.depth()
+    }
+
+    #[must_use]
+    pub fn in_bounds(&self, point: Point3d) -> bool {
+        self.bounds.contains(point)
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point3d) -> Option<&T> {
+        self.in_bounds(point).then(|| &self[point])
+    }
+
+    pub fn get_mut(&mut self, point: Point3d) -> Option<&mut T> {
+        self.in_bounds(point).then(|| &mut self[point])
+    }
+
+    /// # Panics
+    /// If `point` is outside the grid
+    #[must_use]
+    pub fn point_to_idx(&self, point: Point3d) -> usize {
+        assert!(self.in_bounds(point), "{point} is outside the grid");
+        let offset = point - self.bounds.min;
+        ((offset.z * self.height() + offset.y) * self.width() + offset.x) as usize
+    }
+
+    #[must_use]
+    pub fn idx_to_point(&self, idx: usize) -> Point3d {
+        let idx = idx as i32;
+        let (width, height) = (self.width(), self.height());
+        self.bounds.min + Point3d::new(idx % width, idx / width % height, idx / (width * height))
+    }
+
+    /// Builds a grid of the same size with `f` applied to every cell
+    pub fn map<U: Clone>(&self, f: impl FnMut(&T) -> U) -> Vec3d<U> {
+        Vec3d { cells: self.cells.iter().map(f).collect(), bounds: self.bounds }
+    }
+
+    /// Every point in the grid with its value, in reading order
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point3d, &T)> {
+        self.cells.iter().enumerate().map(|(idx, cell)| (self.idx_to_point(idx), cell))
+    }
+
+    pub fn positions(&self, predicate: impl Fn(&T) -> bool) -> impl Iterator<Item = Point3d> {
+        self.enumerate().filter(move |(_, cell)| predicate(cell)).map(|(point, _)| point)
+    }
+
+    /// The neighbors sharing a face with `point` that are inside the grid
+    pub fn neighbors6(&self, point: Point3d) -> impl Iterator<Item = (Point3d, &T)> {
+        point.neighbors6().filter_map(|next| self.get(next).map(|cell| (next, cell)))
+    }
+
+    /// The neighbors sharing a face, edge or corner with `point` that are inside the grid
+    pub fn neighbors26(&self, point: Point3d) -> impl Iterator<Item = (Point3d, &T)> {
+        point.neighbors26().filter_map(|next| self.get(next).map(|cell| (next, cell)))
+    }
+
+    /// The 2d layer across `axis` at coordinate `at`. See [`Self::render_slice`] for which axes the layer keeps.
+    ///
+    /// # Panics
+    /// If `at` is outside the grid along `axis`
+    #[must_use]
+    pub fn slice(&self, axis: Axis, at: i32) -> Vec2d<T> {
+        let rect = self.slice_bounds(axis, at);
+        let cells = rect.points().map(|point| self[axis.unslice(point, at)].clone()).collect();
+        Vec2d::new(cells, rect.width())
+    }
+
+    /// Draws the layer across `axis` at `at` as text, using `cell` to turn each value into a character.
+    /// The layer keeps the other two axes in x, y, z order: a slice across z is drawn with x across and y down,
+    /// across y with x across and z down, and across x with y across and z down.
+    /// Rulers and highlights use the grid's own coordinates.
+    ///
+    /// # Panics
+    /// If `at` is outside the grid along `axis`
+    pub fn render_slice<'a>(&'a self, axis: Axis, at: i32, cell: impl Fn(&T) -> char + 'a) -> Renderer<'a> {
+        Renderer::new(self.slice_bounds(axis, at), move |point| cell(&self[axis.unslice(point, at)]))
+    }
+
+    fn slice_bounds(&self, axis: Axis, at: i32) -> Rect {
+        let (min, max) = (self.bounds.min, self.bounds.max);
+        let range = match axis {
+            Axis::X => min.x ..= max.x,
+            Axis::Y => min.y ..= max.y,
+            Axis::Z => min.z ..= max.z,
+        };
+        assert!(range.contains(&at), "{at} is outside the grid along {axis:?}");
+        Rect::from_corners(axis.slice_point(min), axis.slice_point(max))
+    }
+
+    /// Marks the cells that can be reached from outside the grid by moving between faces
+    /// without passing through a solid cell. Pockets enclosed by solid cells stay `false`.
+    pub fn exterior(&self, is_solid: impl Fn(&T) -> bool) -> Vec3d<bool> {
+        // searches one layer past every side, so the outside is connected all the way around
+        let mut outside = Vec3d::filled(self.bounds.expand(1), false);
+        let start = outside.bounds.min;
+        outside[start] = true;
+        let mut queue = VecDeque::from([start]);
+        while let Some(point) = queue.pop_front() {
+            for next in point.neighbors6() {
+                if outside.get(next) == Some(&false) && self.get(next).is_none_or(|cell| !is_solid(cell)) {
+                    outside[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        let bounds = self.bounds;
+        Vec3d { cells: bounds.points().map(|point| outside[point]).collect(), bounds }
+    }
+
+    /// Number of faces of solid cells that touch a cell that isn't solid, or the outside of the grid.
+    /// Includes the faces of enclosed pockets.
+    pub fn surface_area(&self, is_solid: impl Fn(&T) -> bool) -> usize {
+        self.positions(&is_solid)
+            .flat_map(|point| point.neighbors6())
+            .filter(|&next| self.get(next).is_none_or(|cell| !is_solid(cell)))
+            .count()
+    }
+
+    /// Number of faces of solid cells that can be reached from outside the grid.
+    /// Unlike [`Self::surface_area`], faces of enclosed pockets don't count.
+    pub fn exterior_surface_area(&self, is_solid: impl Fn(&T) -> bool) -> usize {
+        let outside = self.exterior(&is_solid);
+        self.positions(&is_solid)
+            .flat_map(|point| point.neighbors6())
+            .filter(|&next| outside.get(next).is_none_or(|&outside| outside))
+            .count()
+    }
+}
+
+impl Vec3d<bool> {
+
+    /// A grid just big enough to hold every point, with those points `true`.
+    /// Returns `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point3d>) -> Option<Self> {
+        let points = points.into_iter().collect::<Vec<_>>();
+        let mut grid = Self::filled(Cuboid::from_points(points.iter().copied())?, false);
+        points.into_iter().for_each(|point| grid[point] = true);
+        Some(grid)
+    }
+}
+
+impl<T: Clone> Index<Point3d> for Vec3d<T> {
+    type Output = T;
+
+    fn index(&self, index: Point3d) -> &Self::Output {
+        &self.cells[self.point_to_idx(index)]
+    }
+}
+
+impl<T: Clone> IndexMut<Point3d> for Vec3d<T> {
+
+    fn index_mut(&mut self, index: Point3d) -> &mut Self::Output {
+        let idx = self.point_to_idx(index);
+        &mut self.cells[idx]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x3x3 cube of droplets with the center one missing
+    fn hollow_cube() -> Vec3d<bool> {
+        let points = Cuboid::from_corners(Point3d::new(1, 1, 1), Point3d::new(3, 3, 3)).points()
+            .filter(|&point| point != Point3d::new(2, 2, 2));
+        Vec3d::from_points(points).unwrap()
+    }
+
+    #[test]
+    fn test_indexing_and_neighbors() {
+        let mut grid = Vec3d::filled(Cuboid::from_corners(Point3d::new(-1, 0, 2), Point3d::new(1, 2, 5)), 0);
+        assert_eq!((3, 3, 4), (grid.width(), grid.height(), grid.depth()));
+        grid[Point3d::new(1, 2, 5)] = 7;
+        assert_eq!(Some(&7), grid.get(Point3d::new(1, 2, 5)));
+        assert_eq!(None, grid.get(Point3d::new(2, 2, 5)));
+        assert_eq!(Point3d::new(1, 2, 5), grid.idx_to_point(grid.point_to_idx(Point3d::new(1, 2, 5))));
+        assert!(grid.enumerate().map(|(point, _)| point).is_sorted());
+
+        let corner = Point3d::new(-1, 0, 2);
+        assert_eq!(3, grid.neighbors6(corner).count());
+        assert_eq!(7, grid.neighbors26(corner).count());
+        assert_eq!(26, grid.neighbors26(Point3d::new(0, 1, 3)).count());
+    }
+
+    #[test]
+    fn test_surface_area() {
+        let grid = hollow_cube();
+        // the outside of the cube, plus the 6 faces around the hole in the middle
+        assert_eq!(54 + 6, grid.surface_area(|&solid| solid));
+        assert_eq!(54, grid.exterior_surface_area(|&solid| solid));
+        assert!(!grid.exterior(|&solid| solid)[Point3d::new(2, 2, 2)]);
+
+        // two separate droplets don't hide each other's faces
+        let pair = Vec3d::from_points([Point3d::new(0, 0, 0), Point3d::new(2, 0, 0)]).unwrap();
+        assert_eq!(12, pair.exterior_surface_area(|&solid| solid));
+    }
+
+    #[test]
+    fn test_slices() {
+        let mut grid = hollow_cube();
+        grid[Point3d::new(3, 1, 2)] = false;
+        let middle = grid.slice(Axis::Z, 2);
+        assert_eq!(Vec2d::parse("##.\n#.#\n###", |c| c == '#').unwrap(), middle);
+        assert_eq!("##.\n#.#\n###", grid.render_slice(Axis::Z, 2, |&solid| if solid { '#' } else { '.' }).to_string());
+        // across y the rows are z, so the missing cell is on the middle row
+        assert_eq!("###\n##.\n###", grid.render_slice(Axis::Y, 1, |&solid| if solid { '#' } else { '.' }).to_string());
+        assert_eq!(Vec2d::parse("###\n.##\n###", |c| c == '#').unwrap(), grid.slice(Axis::X, 3));
+    }
+}